@@ -1,3 +1,4 @@
 mod matter;
+mod quantity;
 mod reaction;
 mod utils;
@@ -1,2 +1,13 @@
+pub mod acid;
+pub mod concentration;
+pub mod dissociation;
 pub mod element;
+pub mod gas;
+pub mod ion;
+pub mod oxide;
+pub mod ph;
+pub mod solubility;
+pub mod solution;
 pub mod substance;
+pub mod thermodynamics;
+pub mod titration;
@@ -0,0 +1,120 @@
+use super::{
+    ion::Ion,
+    substance::{is_composition, Substance, SubstanceClass},
+};
+use crate::utils::{acid_table::ACIDS, periodic_table::PeriodicTable};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AcidKind {
+    Oxo,
+    Binary,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Strength {
+    Strong,
+    Weak,
+}
+
+#[derive(Debug, Clone)]
+pub struct AcidProperties {
+    pub basicity: u8,
+    pub kind: AcidKind,
+    pub strength: Strength,
+    // pKa of every dissociation step - empty for acids out of the table
+    pub pka: Vec<f64>,
+    pub residue: Ion,
+}
+
+impl Substance {
+    pub fn acid_properties(&self, p_t: &PeriodicTable) -> Option<AcidProperties> {
+        if self.class != SubstanceClass::Acid {
+            return None;
+        }
+
+        let mut residue = self.blocks();
+        let known = ACIDS
+            .iter()
+            .find(|(formula, _, _)| is_composition(&residue, formula, p_t));
+
+        let h = residue.get_mut("H")?;
+        let basicity = match known {
+            Some(acid) => acid.1,
+            None => h.index,
+        };
+        h.index -= basicity;
+        let h_left = h.index;
+        if h_left == 0 {
+            residue.remove("H");
+        }
+
+        let kind = match residue.contains_key("O") {
+            true => AcidKind::Oxo,
+            false => AcidKind::Binary,
+        };
+
+        let pka = known.map_or(vec![], |acid| acid.2.to_vec());
+        let strength = match (pka.first(), kind) {
+            (Some(pka), _) if *pka < 0.0 => Strength::Strong,
+            (Some(_), _) | (None, AcidKind::Binary) => Strength::Weak,
+            // unknown oxoacid: HₙXOₘ is strong when there are
+            // at least 2 O not bound with H
+            (None, AcidKind::Oxo) => match residue["O"].index >= h_left + basicity + 2 {
+                true => Strength::Strong,
+                false => Strength::Weak,
+            },
+        };
+
+        Some(AcidProperties {
+            basicity,
+            kind,
+            strength,
+            pka,
+            residue: Ion::new(residue, -(basicity as i8)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use AcidKind::{Binary, Oxo};
+    use Strength::{Strong, Weak};
+
+    #[test]
+    fn acid_properties() {
+        let p_t = PeriodicTable::new();
+        let acids = [
+            ("H2SO4", 2, Oxo, Strong, "SO4", "sulfate"),
+            ("HCl", 1, Binary, Strong, "Cl", "chloride"),
+            ("H2S", 2, Binary, Weak, "S", "sulfide"),
+            ("HNO3", 1, Oxo, Strong, "NO3", "nitrate"),
+            ("H3PO4", 3, Oxo, Weak, "PO4", "phosphate"),
+            ("H3PO3", 2, Oxo, Weak, "HPO3", "phosphite"),
+            ("H3PO2", 1, Oxo, Weak, "H2PO2", "hypophosphite"),
+            ("CH3COOH", 1, Oxo, Weak, "CH3COO", "acetate"),
+        ];
+
+        for (formula, basicity, kind, strength, residue, name) in acids {
+            let acid = Substance::from_string(formula, &p_t)
+                .unwrap()
+                .acid_properties(&p_t)
+                .unwrap();
+            assert_eq!(basicity, acid.basicity);
+            assert_eq!(kind, acid.kind);
+            assert_eq!(strength, acid.strength);
+            assert_eq!(basicity as usize, acid.pka.len());
+            assert_eq!(residue, acid.residue.formula(&p_t));
+            assert_eq!(Some(name), acid.residue.name(&p_t));
+            assert_eq!(-(basicity as i8), acid.residue.charge);
+        }
+
+        let h2so4 = Substance::from_string("H2SO4", &p_t).unwrap();
+        assert_eq!(
+            "SO4²⁻",
+            h2so4.acid_properties(&p_t).unwrap().residue.to_string()
+        );
+        let naoh = Substance::from_string("NaOH", &p_t).unwrap();
+        assert!(naoh.acid_properties(&p_t).is_none());
+    }
+}
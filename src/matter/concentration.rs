@@ -0,0 +1,67 @@
+use super::substance::Substance;
+use crate::utils::periodic_table::PeriodicTable;
+
+// Mass fraction from which acids count as concentrated
+const CONCENTRATED_FROM: [(&str, f64); 2] = [("HNO3", 0.6), ("H2SO4", 0.7)];
+const DEFAULT_CONCENTRATED_FROM: f64 = 0.6;
+// Solutions below it are very dilute
+const VERY_DILUTE_BELOW: f64 = 0.05;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Concentration {
+    Dilute,
+    Concentrated,
+    // mass fraction of the solute, from 0 to 1
+    MassFraction(f64),
+}
+
+impl Substance {
+    pub fn with_concentration(mut self, concentration: Concentration) -> Self {
+        self.concentration = Some(concentration);
+        self
+    }
+
+    // Solutions are dilute unless it's said otherwise
+    pub fn is_concentrated(&self, p_t: &PeriodicTable) -> bool {
+        match self.concentration {
+            Some(Concentration::Concentrated) => true,
+            Some(Concentration::MassFraction(w)) => {
+                let formula = self.formula(p_t);
+                let from = CONCENTRATED_FROM
+                    .iter()
+                    .find(|(f, _)| *f == formula)
+                    .map_or(DEFAULT_CONCENTRATED_FROM, |c| c.1);
+                w >= from
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_very_dilute(&self) -> bool {
+        matches!(self.concentration, Some(Concentration::MassFraction(w)) if w < VERY_DILUTE_BELOW)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substance_concentration() {
+        let p_t = PeriodicTable::new();
+        let hno3 = Substance::from_string("HNO3", &p_t).unwrap();
+        let h2so4 = Substance::from_string("H2SO4", &p_t).unwrap();
+
+        assert!(!hno3.is_concentrated(&p_t));
+        let conc = hno3.clone().with_concentration(Concentration::Concentrated);
+        assert!(conc.is_concentrated(&p_t));
+        let w = hno3
+            .clone()
+            .with_concentration(Concentration::MassFraction(0.65));
+        assert!(w.is_concentrated(&p_t) && !w.is_very_dilute());
+        let w = h2so4.with_concentration(Concentration::MassFraction(0.65));
+        assert!(!w.is_concentrated(&p_t));
+        let w = hno3.with_concentration(Concentration::MassFraction(0.03));
+        assert!(!w.is_concentrated(&p_t) && w.is_very_dilute());
+    }
+}
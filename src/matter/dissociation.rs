@@ -0,0 +1,217 @@
+use std::fmt;
+
+use super::{
+    acid::Strength,
+    ion::{ions_to_string, subtract, superscript_charge, Ion},
+    solubility::Solubility,
+    substance::{Substance, SubstanceClass},
+};
+use crate::utils::periodic_table::PeriodicTable;
+
+#[derive(Debug, Clone)]
+pub struct DissociationStep {
+    // the one which dissociates, molecule itself has zero charge
+    pub particle: Ion,
+    pub ions: Vec<(Ion, u8)>,
+    pub reversible: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Dissociation {
+    pub electrolyte: Strength,
+    // all the steps together: H₂SO₄ -> 2H⁺ + SO₄²⁻
+    pub ions: Vec<(Ion, u8)>,
+    pub steps: Vec<DissociationStep>,
+}
+
+impl fmt::Display for DissociationStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arrow = match self.reversible {
+            true => "⇌",
+            false => "→",
+        };
+        let p_t = PeriodicTable::new();
+        write!(
+            f,
+            "{}{} {} {}",
+            self.particle.formula(&p_t),
+            superscript_charge(self.particle.charge),
+            arrow,
+            ions_to_string(&self.ions, &p_t)
+        )
+    }
+}
+
+impl fmt::Display for Dissociation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arrow = match self.electrolyte {
+            Strength::Strong => "→",
+            Strength::Weak => "⇌",
+        };
+        let p_t = PeriodicTable::new();
+        write!(
+            f,
+            "{} {} {}",
+            self.steps[0].particle.formula(&p_t),
+            arrow,
+            ions_to_string(&self.ions, &p_t)
+        )
+    }
+}
+
+impl Substance {
+    // None for non-electrolytes and for the ones which don't dissolve
+    pub fn dissociation(&self, p_t: &PeriodicTable) -> Option<Dissociation> {
+        let ions = self.ions(p_t)?;
+        if let Some(Solubility::Insoluble | Solubility::Decomposes | Solubility::NotExists) =
+            self.solubility(p_t)
+        {
+            return None;
+        }
+
+        let h = Ion::from_string("H", 1, p_t).ok()?;
+        let oh = Ion::from_string("OH", -1, p_t).ok()?;
+        let molecule = Ion::new(self.blocks(), 0);
+
+        let (electrolyte, steps) = match self.class {
+            // H⁺ goes away one by one, only the first step of strong acid is complete
+            SubstanceClass::Acid => {
+                let acid = self.acid_properties(p_t)?;
+                let mut steps = Vec::new();
+                let mut particle = molecule;
+                for i in 0..acid.basicity {
+                    let next = Ion::new(
+                        subtract(&particle.blocks, &h.blocks, 1)?,
+                        particle.charge - 1,
+                    );
+                    steps.push(DissociationStep {
+                        particle,
+                        ions: vec![(h.clone(), 1), (next.clone(), 1)],
+                        reversible: acid.strength == Strength::Weak || i > 0,
+                    });
+                    particle = next;
+                }
+                (acid.strength, steps)
+            }
+            // OH⁻ goes away one by one, alkalis dissociate completely
+            SubstanceClass::Base => {
+                let electrolyte = match self.me.is_empty() {
+                    true => Strength::Weak, // NH₄OH
+                    false => Strength::Strong,
+                };
+                let n_oh = ions.iter().find(|(ion, _)| *ion == oh)?.1;
+                let mut steps = Vec::new();
+                let mut particle = molecule;
+                for _ in 0..n_oh {
+                    let next = Ion::new(
+                        subtract(&particle.blocks, &oh.blocks, 1)?,
+                        particle.charge + 1,
+                    );
+                    steps.push(DissociationStep {
+                        particle,
+                        ions: vec![(next.clone(), 1), (oh.clone(), 1)],
+                        reversible: electrolyte == Strength::Weak,
+                    });
+                    particle = next;
+                }
+                (electrolyte, steps)
+            }
+            // salts dissociate completely, anions of acid salts - as weak acids
+            _ => {
+                let mut steps = vec![DissociationStep {
+                    particle: molecule,
+                    ions: ions.clone(),
+                    reversible: false,
+                }];
+                for (anion, _) in ions.iter().filter(|(ion, _)| ion.charge < 0) {
+                    let mut particle = anion.clone();
+                    while let Some(next) = subtract(&particle.blocks, &h.blocks, 1) {
+                        let next = Ion::new(next, particle.charge - 1);
+                        if next.name(p_t).is_none() {
+                            break;
+                        }
+                        steps.push(DissociationStep {
+                            particle,
+                            ions: vec![(h.clone(), 1), (next.clone(), 1)],
+                            reversible: true,
+                        });
+                        particle = next;
+                    }
+                }
+                (Strength::Strong, steps)
+            }
+        };
+
+        Some(Dissociation {
+            electrolyte,
+            ions,
+            steps,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substance_dissociation() {
+        let p_t = PeriodicTable::new();
+        let substances = [
+            ("NaCl", Strength::Strong, vec!["NaCl → Na⁺ + Cl⁻"]),
+            (
+                "H2SO4",
+                Strength::Strong,
+                vec!["H2SO4 → H⁺ + HSO4⁻", "HSO4⁻ ⇌ H⁺ + SO4²⁻"],
+            ),
+            (
+                "H3PO4",
+                Strength::Weak,
+                vec![
+                    "H3PO4 ⇌ H⁺ + H2PO4⁻",
+                    "H2PO4⁻ ⇌ H⁺ + HPO4²⁻",
+                    "HPO4²⁻ ⇌ H⁺ + PO4³⁻",
+                ],
+            ),
+            (
+                "Ba(OH)2",
+                Strength::Strong,
+                vec!["Ba(OH)2 → BaOH⁺ + OH⁻", "BaOH⁺ → Ba²⁺ + OH⁻"],
+            ),
+            ("NH4OH", Strength::Weak, vec!["NH4OH ⇌ NH4⁺ + OH⁻"]),
+            (
+                "NaHCO3",
+                Strength::Strong,
+                vec!["NaHCO3 → Na⁺ + HCO3⁻", "HCO3⁻ ⇌ H⁺ + CO3²⁻"],
+            ),
+            ("CH3COOH", Strength::Weak, vec!["CH3COOH ⇌ H⁺ + CH3COO⁻"]),
+        ];
+
+        for (formula, electrolyte, steps) in substances {
+            let d = Substance::from_string(formula, &p_t)
+                .unwrap()
+                .dissociation(&p_t)
+                .unwrap();
+            assert_eq!(electrolyte, d.electrolyte);
+            let d_steps: Vec<String> = d.steps.iter().map(|s| s.to_string()).collect();
+            assert_eq!(steps, d_steps);
+        }
+
+        let h2so4 = Substance::from_string("H2SO4", &p_t).unwrap();
+        assert_eq!(
+            "H2SO4 → 2H⁺ + SO4²⁻",
+            h2so4.dissociation(&p_t).unwrap().to_string()
+        );
+        let al2so43 = Substance::from_string("Al2(SO4)3", &p_t).unwrap();
+        assert_eq!(
+            "Al2(SO4)3 → 2Al³⁺ + 3SO4²⁻",
+            al2so43.dissociation(&p_t).unwrap().to_string()
+        );
+
+        // insoluble and non-electrolytes
+        for formula in ["BaSO4", "Cu(OH)2", "H2SiO3", "CO2", "Fe"] {
+            let s = Substance::from_string(formula, &p_t).unwrap();
+            assert!(s.dissociation(&p_t).is_none());
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use super::substance::Substance;
+use crate::quantity::{
+    Amount, Density, Entropy, Mass, MolarMass, Pressure, Temperature, Volume, NORMAL_PRESSURE,
+};
+
+// J/(mol·K), the entropy of 1 mol
+pub const GAS_CONSTANT: Entropy = Entropy::from_joules_per_kelvin(8.314462618);
+const AIR_MOLAR_MASS: MolarMass = MolarMass::from_grams_per_mole(28.96);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GasConditions {
+    pub temperature: Temperature,
+    pub pressure: Pressure,
+}
+
+// 0 °C and 1 atm
+pub const STP: GasConditions = GasConditions {
+    temperature: Temperature::from_celsius(0.0),
+    pressure: NORMAL_PRESSURE,
+};
+
+// 20 °C and 1 atm
+pub const NTP: GasConditions = GasConditions {
+    temperature: Temperature::from_celsius(20.0),
+    pressure: NORMAL_PRESSURE,
+};
+
+// Ideal gases in volume (= mole) fractions
+#[derive(Debug, Clone)]
+pub struct GasMixture {
+    pub components: Vec<(Substance, f64)>,
+}
+
+impl GasConditions {
+    // V of 1 mol = RT / P
+    pub fn molar_volume(&self) -> Volume {
+        GAS_CONSTANT * self.temperature / self.pressure
+    }
+
+    // PV = nRT
+    pub fn volume(&self, amount: Amount) -> Volume {
+        self.molar_volume() * amount.moles()
+    }
+
+    pub fn amount(&self, volume: Volume) -> Amount {
+        Amount::from_moles(volume / self.molar_volume())
+    }
+
+    // ρ = PM / RT
+    pub fn density(&self, molar_mass: MolarMass) -> Density {
+        let mole = Amount::from_moles(1.0);
+        mole * molar_mass / self.volume(mole)
+    }
+}
+
+impl Substance {
+    pub fn gas_volume(&self, mass: Mass, conditions: &GasConditions) -> Volume {
+        conditions.volume(mass / self.molar_mass())
+    }
+
+    pub fn gas_density(&self, conditions: &GasConditions) -> Density {
+        conditions.density(self.molar_mass())
+    }
+
+    // D by H₂ is M / 2
+    pub fn relative_density(&self, other: &Substance) -> f64 {
+        self.molar_mass() / other.molar_mass()
+    }
+
+    pub fn relative_density_to_air(&self) -> f64 {
+        self.molar_mass() / AIR_MOLAR_MASS
+    }
+}
+
+impl GasMixture {
+    // Volumes or their fractions, they are normalized to the sum of 1
+    pub fn by_volume(components: Vec<(Substance, f64)>) -> Result<Self, &'static str> {
+        let total: f64 = components.iter().map(|(_, part)| part).sum();
+        if components.iter().any(|(_, part)| *part < 0.0) || total <= 0.0 {
+            return Err("Parts of the mixture have to be positive");
+        }
+        Ok(Self {
+            components: components
+                .into_iter()
+                .map(|(s, part)| (s, part / total))
+                .collect(),
+        })
+    }
+
+    // Masses or their fractions: the mole of each part is w / M
+    pub fn by_mass(components: Vec<(Substance, f64)>) -> Result<Self, &'static str> {
+        Self::by_volume(
+            components
+                .into_iter()
+                .map(|(s, w)| {
+                    let moles = w / s.molar_mass().grams_per_mole();
+                    (s, moles)
+                })
+                .collect(),
+        )
+    }
+
+    pub fn volume_fractions(&self) -> Vec<f64> {
+        self.components.iter().map(|(_, phi)| *phi).collect()
+    }
+
+    pub fn mass_fractions(&self) -> Vec<f64> {
+        let m = self.molar_mass();
+        self.components
+            .iter()
+            .map(|(s, phi)| s.molar_mass() * *phi / m)
+            .collect()
+    }
+
+    // M = Σ φᵢMᵢ
+    pub fn molar_mass(&self) -> MolarMass {
+        self.components
+            .iter()
+            .fold(MolarMass::default(), |m, (s, phi)| {
+                m + s.molar_mass() * *phi
+            })
+    }
+
+    pub fn density(&self, conditions: &GasConditions) -> Density {
+        conditions.density(self.molar_mass())
+    }
+
+    pub fn relative_density(&self, other: &Substance) -> f64 {
+        self.molar_mass() / other.molar_mass()
+    }
+
+    pub fn relative_density_to_air(&self) -> f64 {
+        self.molar_mass() / AIR_MOLAR_MASS
+    }
+
+    // Dalton's law: pᵢ = φᵢP
+    pub fn partial_pressures(&self, total: Pressure) -> Vec<Pressure> {
+        self.components
+            .iter()
+            .map(|(_, phi)| total * *phi)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::periodic_table::PeriodicTable;
+
+    #[test]
+    fn substance_gas() {
+        let p_t = PeriodicTable::new();
+        let close = |a: f64, b: f64| (a - b).abs() < 0.01;
+        let co2 = Substance::from_string("CO2", &p_t).unwrap();
+        let h2 = Substance::from_string("H2", &p_t).unwrap();
+
+        assert!(close(22.41, STP.molar_volume().litres()));
+        assert!(close(24.06, NTP.molar_volume().litres()));
+        assert!(close(
+            11.2,
+            co2.gas_volume(Mass::from_grams(22.005), &STP).litres()
+        ));
+        assert!(close(1.96, co2.gas_density(&STP).grams_per_litre()));
+        assert!(close(21.83, co2.relative_density(&h2)));
+        assert!(close(1.52, co2.relative_density_to_air()));
+
+        let hot = GasConditions {
+            temperature: "273 °C".parse().unwrap(),
+            pressure: "2 atm".parse().unwrap(),
+        };
+        assert!(close(22.41, hot.molar_volume().litres()));
+        assert!(close(0.5, hot.amount(Volume::from_litres(11.2)).moles()));
+    }
+
+    #[test]
+    fn gas_mixture() {
+        let p_t = PeriodicTable::new();
+        let close = |a: f64, b: f64| (a - b).abs() < 0.01;
+        let gas = |formula: &str| Substance::from_string(formula, &p_t).unwrap();
+
+        let air =
+            GasMixture::by_volume(vec![(gas("N2"), 78.0), (gas("O2"), 21.0), (gas("Ar"), 1.0)])
+                .unwrap();
+        assert!(close(28.96, air.molar_mass().grams_per_mole()));
+        assert!(close(1.0, air.relative_density_to_air()));
+        assert!(close(0.23, air.mass_fractions()[1]));
+        assert!(close(1.29, air.density(&STP).grams_per_litre()));
+        assert!(close(14.37, air.relative_density(&gas("H2"))));
+
+        let detonating = GasMixture::by_mass(vec![(gas("H2"), 0.5), (gas("O2"), 0.5)]).unwrap();
+        assert!(close(0.94, detonating.volume_fractions()[0]));
+        let pressures = detonating.partial_pressures(Pressure::from_kilopascals(100.0));
+        assert!(close(94.07, pressures[0].kilopascals()));
+        assert!(GasMixture::by_volume(vec![(gas("H2"), -1.0)]).is_err());
+    }
+}
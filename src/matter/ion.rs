@@ -0,0 +1,362 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::substance::{
+    balance_oxidation_states, composition_ratio, is_composition, Substance, SubstanceBlock,
+    SubstanceClass,
+};
+use crate::utils::{
+    acid_table::ACIDS, format_util::superscript, ion_table::IONS, math_util::gcd, parser,
+    periodic_table::PeriodicTable,
+};
+
+#[derive(Debug, Clone)]
+pub struct Ion {
+    pub blocks: HashMap<String, SubstanceBlock>,
+    pub charge: i8,
+}
+
+impl PartialEq for Ion {
+    fn eq(&self, other: &Self) -> bool {
+        self.charge == other.charge && composition_ratio(&self.blocks, &other.blocks) == Some(1)
+    }
+}
+
+impl fmt::Display for Ion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let p_t = PeriodicTable::new();
+        write!(
+            f,
+            "{}{}",
+            self.formula(&p_t),
+            superscript_charge(self.charge)
+        )
+    }
+}
+
+impl Ion {
+    pub fn new(blocks: HashMap<String, SubstanceBlock>, charge: i8) -> Self {
+        Self { blocks, charge }
+    }
+
+    pub fn from_string(s: &str, charge: i8, p_t: &PeriodicTable) -> Result<Self, &'static str> {
+        let mut blocks = parser::collect_substance(s, p_t)?;
+        if !balance_oxidation_states(&mut blocks, charge) {
+            return Err("Oxidation states of the ion can't be determined");
+        }
+        Ok(Self::new(blocks, charge))
+    }
+
+    pub fn formula(&self, p_t: &PeriodicTable) -> String {
+        if let Some(known) = self.known(p_t) {
+            return known.0.to_string();
+        }
+
+        if self.charge == 0 {
+            // molecule of known acid keeps its usual formula - CH₃COOH
+            if let Some(acid) = ACIDS
+                .iter()
+                .find(|(formula, _, _)| is_composition(&self.blocks, formula, p_t))
+            {
+                return acid.0.to_string();
+            }
+            // salts and bases are written by their ions - Al₂(SO₄)₃
+            if let Ok(s) = Substance::from_elements(self.blocks.clone(), p_t) {
+                if let (SubstanceClass::Base | SubstanceClass::Salt, Some(ions)) =
+                    (s.class, s.ions(p_t))
+                {
+                    return formula_of_ions(&ions, p_t);
+                }
+            }
+        }
+
+        // hydroxo particles: Me(OH)ₙ or NH₄OH
+        let n_oh = match (self.blocks.get("O"), self.blocks.get("H")) {
+            (Some(o), Some(h)) => o.index.min(h.index),
+            _ => 0,
+        };
+        if n_oh > 0 {
+            if let Some(rest) = Ion::from_string("OH", -1, p_t)
+                .ok()
+                .and_then(|oh| subtract(&self.blocks, &oh.blocks, n_oh))
+            {
+                let rest = Ion::new(rest, self.charge + n_oh as i8);
+                if !rest.blocks.is_empty()
+                    && (rest.name(p_t) == Some("ammonium")
+                        || rest
+                            .blocks
+                            .iter()
+                            .all(|(n, sb)| n != "H" && sb.element.is_me()))
+                {
+                    return match n_oh {
+                        1 => format!("{}OH", rest.formula(p_t)),
+                        n => format!("{}(OH){}", rest.formula(p_t), n),
+                    };
+                }
+            }
+        }
+
+        // others: Me first, O last (but OF₂) and others by electronegativity,
+        // H goes first if there is no Me, but NH₃ or CH₄ are exceptions
+        let has_me = self
+            .blocks
+            .iter()
+            .any(|(n, sb)| n != "H" && sb.element.is_me());
+        let hydrogen_last = self.blocks.len() == 2
+            && self
+                .blocks
+                .values()
+                .any(|sb| (14..=15).contains(&sb.element.group));
+        let h_first = !(has_me || hydrogen_last);
+        let order = |name: &str, sb: &SubstanceBlock| match name {
+            "H" if h_first => 0,
+            "H" => 3,
+            "O" if !self.blocks.contains_key("F") => 4,
+            _ if sb.element.is_me() => 1,
+            _ => 2,
+        };
+        let mut sbs: Vec<(&String, &SubstanceBlock)> = self.blocks.iter().collect();
+        sbs.sort_by(|a, b| {
+            order(a.0, a.1).cmp(&order(b.0, b.1)).then(
+                a.1.element
+                    .electronegativity
+                    .total_cmp(&b.1.element.electronegativity),
+            )
+        });
+        let mut res = String::new();
+        for (name, sb) in sbs {
+            res.push_str(name);
+            if sb.index > 1 {
+                res.push_str(&sb.index.to_string());
+            }
+        }
+        res
+    }
+
+    pub fn name(&self, p_t: &PeriodicTable) -> Option<&'static str> {
+        self.known(p_t).map(|known| known.2)
+    }
+
+    fn known(&self, p_t: &PeriodicTable) -> Option<&'static (&'static str, i8, &'static str)> {
+        IONS.iter().find(|(formula, charge, _)| {
+            *charge == self.charge && is_composition(&self.blocks, formula, p_t)
+        })
+    }
+}
+
+// Substance as ions with their numbers: Al₂(SO₄)₃ -> 2Al³⁺ + 3SO₄²⁻
+impl Substance {
+    pub fn formula(&self, p_t: &PeriodicTable) -> String {
+        Ion::new(self.blocks(), 0).formula(p_t)
+    }
+
+    pub fn ions(&self, p_t: &PeriodicTable) -> Option<Vec<(Ion, u8)>> {
+        match self.class {
+            SubstanceClass::Acid => {
+                let acid = self.acid_properties(p_t)?;
+                let h = Ion::from_string("H", 1, p_t).ok()?;
+                Some(vec![(h, acid.basicity), (acid.residue, 1)])
+            }
+            SubstanceClass::Base | SubstanceClass::Salt if self.me.is_empty() => {
+                ammonium_ions(&self.anti_me, p_t)
+            }
+            SubstanceClass::Base | SubstanceClass::Salt => {
+                let mut ions = Vec::new();
+                let mut total = 0;
+                let mut me: Vec<(&String, &SubstanceBlock)> = self.me.iter().collect();
+                me.sort_by_key(|(_, sb)| sb.element.charge);
+                for (name, sb) in me {
+                    let cation = SubstanceBlock::new(sb.element.clone(), 1, sb.oxidation_state);
+                    let cation =
+                        Ion::new(HashMap::from([(name.clone(), cation)]), sb.oxidation_state);
+                    total += sb.oxidation_state as i16 * sb.index as i16;
+                    ions.push((cation, sb.index));
+                }
+                ions.extend(anions(&self.anti_me, total, p_t)?);
+                Some(ions)
+            }
+            _ => None,
+        }
+    }
+}
+
+// NH₄⁺ instead of Me: the number of NH₄ is the one which leaves named anions
+pub fn ammonium_ions(
+    sbs: &HashMap<String, SubstanceBlock>,
+    p_t: &PeriodicTable,
+) -> Option<Vec<(Ion, u8)>> {
+    let nh4 = Ion::from_string("NH4", 1, p_t).ok()?;
+    for n in 1..=sbs.get("N")?.index {
+        let rest = match subtract(sbs, &nh4.blocks, n) {
+            Some(rest) => rest,
+            None => break,
+        };
+        if let Some(anions) = anions(&rest, n as i16, p_t) {
+            if anions.iter().all(|a| a.0.name(p_t).is_some()) {
+                let mut ions = vec![(nh4, n)];
+                ions.extend(anions);
+                return Some(ions);
+            }
+        }
+    }
+    None
+}
+
+// anions compensate the total charge of cations,
+// basic salts - Al(OH)CO₃ - have OH⁻ besides the anion
+fn anions(
+    rest: &HashMap<String, SubstanceBlock>,
+    total: i16,
+    p_t: &PeriodicTable,
+) -> Option<Vec<(Ion, u8)>> {
+    if rest.is_empty() || total <= 0 {
+        return None;
+    }
+    if let Some(anion) = single_anion(rest, total, true, p_t) {
+        return Some(vec![anion]);
+    }
+
+    let oh = Ion::from_string("OH", -1, p_t).ok()?;
+    for j in 1..total {
+        let part = match subtract(rest, &oh.blocks, j as u8) {
+            Some(part) => part,
+            None => break,
+        };
+        if let Some(anion) = single_anion(&part, total - j, true, p_t) {
+            return Some(vec![(oh, j as u8), anion]);
+        }
+    }
+
+    single_anion(rest, total, false, p_t).map(|anion| vec![anion])
+}
+
+// rest = k same anions, the largest k goes first: CaCl₂ - 2Cl⁻ not Cl₂²⁻
+fn single_anion(
+    rest: &HashMap<String, SubstanceBlock>,
+    total: i16,
+    known_only: bool,
+    p_t: &PeriodicTable,
+) -> Option<(Ion, u8)> {
+    let g = rest.values().fold(0, |g, sb| gcd(g, sb.index));
+    for k in (1..=g).rev() {
+        if g % k != 0 || total % k as i16 != 0 {
+            continue;
+        }
+
+        let mut blocks: HashMap<String, SubstanceBlock> = rest.clone();
+        for sb in blocks.values_mut() {
+            sb.index /= k;
+        }
+        let charge = -(total / k as i16) as i8;
+        balance_oxidation_states(&mut blocks, charge);
+
+        let anion = Ion::new(blocks, charge);
+        if !known_only || anion.name(p_t).is_some() {
+            return Some((anion, k));
+        }
+    }
+    None
+}
+
+// sbs without n parts, None if there are not enough elements
+pub fn subtract(
+    sbs: &HashMap<String, SubstanceBlock>,
+    part: &HashMap<String, SubstanceBlock>,
+    n: u8,
+) -> Option<HashMap<String, SubstanceBlock>> {
+    let mut rest = sbs.clone();
+    for (name, p) in part {
+        let sb = rest.get_mut(name)?;
+        sb.index = sb.index.checked_sub(p.index * n)?;
+        if sb.index == 0 {
+            rest.remove(name);
+        }
+    }
+    Some(rest)
+}
+
+// cations then anions, parentheses for several complex ions,
+// organic anions (with C-C bond) go first: CH₃COONa
+fn formula_of_ions(ions: &[(Ion, u8)], p_t: &PeriodicTable) -> String {
+    let organic = |ion: &Ion| ion.charge < 0 && ion.blocks.get("C").is_some_and(|c| c.index > 1);
+    let mut ions = ions.to_vec();
+    if ions.iter().any(|(ion, _)| organic(ion)) {
+        ions.sort_by_key(|(ion, _)| !organic(ion));
+    }
+
+    let mut res = String::new();
+    for (ion, n) in &ions {
+        let formula = ion.formula(p_t);
+        let atoms: u8 = ion.blocks.values().map(|sb| sb.index).sum();
+        match (n, atoms) {
+            (1, _) => res.push_str(&formula),
+            (_, 1) => res.push_str(&format!("{}{}", formula, n)),
+            _ => res.push_str(&format!("({}){}", formula, n)),
+        }
+    }
+    res
+}
+
+// 2H⁺ + SO₄²⁻
+pub fn ions_to_string(ions: &[(Ion, u8)], p_t: &PeriodicTable) -> String {
+    let mut res = Vec::new();
+    for (ion, n) in ions {
+        let ion = format!("{}{}", ion.formula(p_t), superscript_charge(ion.charge));
+        match n {
+            1 => res.push(ion),
+            n => res.push(format!("{}{}", n, ion)),
+        }
+    }
+    res.join(" + ")
+}
+
+pub fn superscript_charge(charge: i8) -> String {
+    let mut res = String::new();
+    if charge.unsigned_abs() > 1 {
+        res.push_str(&superscript(charge.unsigned_abs() as u16));
+    }
+    match charge {
+        0 => (),
+        1.. => res.push('⁺'),
+        _ => res.push('⁻'),
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substance_ions() {
+        let p_t = PeriodicTable::new();
+        let substances = [
+            ("NaCl", vec![("Na⁺", 1), ("Cl⁻", 1)]),
+            ("H2SO4", vec![("H⁺", 2), ("SO4²⁻", 1)]),
+            ("Ca(OH)2", vec![("Ca²⁺", 1), ("OH⁻", 2)]),
+            ("Al2(SO4)3", vec![("Al³⁺", 2), ("SO4²⁻", 3)]),
+            ("CaCl2", vec![("Ca²⁺", 1), ("Cl⁻", 2)]),
+            ("KMnO4", vec![("K⁺", 1), ("MnO4⁻", 1)]),
+            ("NaHCO3", vec![("Na⁺", 1), ("HCO3⁻", 1)]),
+            ("Al(OH)CO3", vec![("Al³⁺", 1), ("OH⁻", 1), ("CO3²⁻", 1)]),
+            ("(NH4)2SO4", vec![("NH4⁺", 2), ("SO4²⁻", 1)]),
+            ("NH4NO3", vec![("NH4⁺", 1), ("NO3⁻", 1)]),
+        ];
+
+        for (formula, expected) in substances {
+            let ions: Vec<(String, u8)> = Substance::from_string(formula, &p_t)
+                .unwrap()
+                .ions(&p_t)
+                .unwrap()
+                .iter()
+                .map(|(ion, n)| (ion.to_string(), *n))
+                .collect();
+            for (ion, n) in expected {
+                assert!(ions.contains(&(ion.to_string(), n)), "{formula}: {ion}");
+            }
+        }
+
+        let so2 = Substance::from_string("SO2", &p_t).unwrap();
+        assert!(so2.ions(&p_t).is_none());
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use super::{
+    ion::Ion,
+    substance::{balance_oxidation_states, Substance, SubstanceBlock, SubstanceClass},
+};
+use crate::utils::{acid_table::ACIDS, parser, periodic_table::PeriodicTable};
+
+// Metals which oxides in +2 state are amphoteric, not basic
+const AMPHOTERIC_2: [&str; 4] = ["Be", "Zn", "Sn", "Pb"];
+// Non-salt-forming oxides: CO, N₂O, NO, SiO
+const INDIFFERENT: [(&str, i8); 4] = [("C", 2), ("N", 1), ("N", 2), ("Si", 2)];
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum OxideKind {
+    Basic,
+    Amphoteric,
+    Acidic,
+    Indifferent,
+}
+
+impl Substance {
+    // Oxide character is given by its element and its oxidation state
+    pub fn oxide_kind(&self) -> Option<OxideKind> {
+        let (name, sb) = self.oxide_element()?;
+        let kind = match (sb.element.is_me(), sb.oxidation_state) {
+            // water has its own rules
+            _ if name == "H" => OxideKind::Indifferent,
+            (true, 5..) => OxideKind::Acidic,
+            (true, 3..=4) => OxideKind::Amphoteric,
+            (true, 2) if AMPHOTERIC_2.contains(&name) => OxideKind::Amphoteric,
+            (true, _) => OxideKind::Basic,
+            (false, state) if INDIFFERENT.contains(&(name, state)) => OxideKind::Indifferent,
+            (false, _) => OxideKind::Acidic,
+        };
+        Some(kind)
+    }
+
+    // The acid which has the same element in the same state: SO₂ - H₂SO₃, SO₃ - H₂SO₄
+    pub fn oxide_acid(&self, p_t: &PeriodicTable) -> Option<Substance> {
+        if self.oxide_kind()? != OxideKind::Acidic {
+            return None;
+        }
+        let (name, sb) = self.oxide_element()?;
+
+        let known = ACIDS.iter().find_map(|(formula, _, _)| {
+            let mut acid = parser::collect_substance(formula, p_t).ok()?;
+            if acid.len() != 3 || !acid.contains_key("O") || !acid.contains_key(name) {
+                return None;
+            }
+            balance_oxidation_states(&mut acid, 0);
+            (acid[name].oxidation_state == sb.oxidation_state).then_some(acid)
+        });
+
+        // the others are meta acids: HₙXOₘ with the least n,
+        // non-metals have them only in states of the parity of their group (no acid of NO₂)
+        let acid = match known {
+            Some(acid) => acid,
+            None if !sb.element.is_me()
+                && (sb.element.group as i8 - sb.oxidation_state) % 2 != 0 =>
+            {
+                return None
+            }
+            None => {
+                let (n, m) = meta_indexes(sb.oxidation_state);
+                let mut acid = HashMap::from([
+                    (
+                        name.to_string(),
+                        SubstanceBlock::new(sb.element.clone(), 1, 0),
+                    ),
+                    (
+                        "H".to_string(),
+                        SubstanceBlock::new(p_t.get("H")?.clone(), n, 0),
+                    ),
+                    (
+                        "O".to_string(),
+                        SubstanceBlock::new(p_t.get("O")?.clone(), m, 0),
+                    ),
+                ]);
+                balance_oxidation_states(&mut acid, 0);
+                acid
+            }
+        };
+
+        Substance::from_elements(acid, p_t)
+            .ok()
+            .filter(|acid| acid.class == SubstanceClass::Acid)
+    }
+
+    // The anion which oxide gives with bases: SO₃ - SO₄²⁻, Al₂O₃ - AlO₂⁻
+    pub fn oxide_residue(&self, p_t: &PeriodicTable) -> Option<Ion> {
+        match self.oxide_kind()? {
+            OxideKind::Acidic => Some(self.oxide_acid(p_t)?.acid_properties(p_t)?.residue),
+            OxideKind::Amphoteric => {
+                let (name, sb) = self.oxide_element()?;
+                let (n, m) = meta_indexes(sb.oxidation_state);
+                let mut blocks = HashMap::from([
+                    (
+                        name.to_string(),
+                        SubstanceBlock::new(sb.element.clone(), 1, 0),
+                    ),
+                    (
+                        "O".to_string(),
+                        SubstanceBlock::new(p_t.get("O")?.clone(), m, 0),
+                    ),
+                ]);
+                balance_oxidation_states(&mut blocks, -(n as i8));
+                Some(Ion::new(blocks, -(n as i8)))
+            }
+            _ => None,
+        }
+    }
+
+    // The cation which oxide gives with acids: CuO - Cu²⁺
+    pub fn oxide_cation(&self) -> Option<Ion> {
+        if let OxideKind::Acidic | OxideKind::Indifferent = self.oxide_kind()? {
+            return None;
+        }
+        let (name, sb) = self.oxide_element()?;
+        let cation = SubstanceBlock::new(sb.element.clone(), 1, sb.oxidation_state);
+        Some(Ion::new(
+            HashMap::from([(name.to_string(), cation)]),
+            sb.oxidation_state,
+        ))
+    }
+
+    fn oxide_element(&self) -> Option<(&str, &SubstanceBlock)> {
+        if self.class != SubstanceClass::Oxide {
+            return None;
+        }
+        let mut elements = self.me.iter().chain(self.anti_me.iter());
+        let (name, sb) = elements.find(|(name, _)| *name != "O")?;
+        Some((name.as_str(), sb))
+    }
+}
+
+// HₙXOₘ for X in the state: odd - HXOₘ, even - H₂XOₘ
+fn meta_indexes(oxidation_state: i8) -> (u8, u8) {
+    let n = 2 - (oxidation_state % 2) as u8;
+    (n, (oxidation_state as u8 + n) / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use OxideKind::{Acidic, Amphoteric, Basic, Indifferent};
+
+    #[test]
+    fn oxide_kind() {
+        let p_t = PeriodicTable::new();
+        let oxides = [
+            ("Na2O", Basic, None, Some("Na⁺")),
+            ("CuO", Basic, None, Some("Cu²⁺")),
+            ("ZnO", Amphoteric, Some("ZnO2²⁻"), Some("Zn²⁺")),
+            ("Al2O3", Amphoteric, Some("AlO2⁻"), Some("Al³⁺")),
+            ("CrO3", Acidic, Some("CrO4²⁻"), None),
+            ("SO2", Acidic, Some("SO3²⁻"), None),
+            ("SO3", Acidic, Some("SO4²⁻"), None),
+            ("P2O5", Acidic, Some("PO4³⁻"), None),
+            ("N2O5", Acidic, Some("NO3⁻"), None),
+            ("Cl2O7", Acidic, Some("ClO4⁻"), None),
+            ("NO2", Acidic, None, None),
+            ("CO", Indifferent, None, None),
+        ];
+
+        for (formula, kind, residue, cation) in oxides {
+            let oxide = Substance::from_string(formula, &p_t).unwrap();
+            assert_eq!(Some(kind), oxide.oxide_kind(), "{formula}");
+            let to_string = |ion: Ion| ion.to_string();
+            assert_eq!(residue, oxide.oxide_residue(&p_t).map(to_string).as_deref());
+            assert_eq!(cation, oxide.oxide_cation().map(to_string).as_deref());
+        }
+
+        let acid = Substance::from_string("SiO2", &p_t)
+            .unwrap()
+            .oxide_acid(&p_t);
+        assert_eq!(Some("H2SiO3".to_string()), acid.map(|a| a.formula(&p_t)));
+        let naoh = Substance::from_string("NaOH", &p_t).unwrap();
+        assert!(naoh.oxide_kind().is_none());
+    }
+}
@@ -0,0 +1,374 @@
+use super::{
+    acid::Strength,
+    ion::{subtract, Ion},
+    substance::{composition_ratio, Substance, SubstanceClass},
+};
+use crate::{
+    quantity::Molarity,
+    reaction::half_reaction::Medium,
+    utils::{acid_table::ACIDS, base_table::WEAK_BASES, parser, periodic_table::PeriodicTable},
+};
+
+// pKw of water at 25°C
+pub const PKW: f64 = 14.0;
+// acids out of the table which are strong
+const STRONG_PKA: f64 = -10.0;
+// ions with pKa or pKb above it hardly change the pH of water
+const HYDROLYSIS_PK: f64 = 11.5;
+// salts of a weak acid and a weak base are neutral when Ka and Kb are that close
+const NEUTRAL_DELTA_PK: f64 = 0.5;
+const SOLVER_STEPS: usize = 100;
+
+// Acid with all its conjugate bases: H₃PO₄ / H₂PO₄⁻ / HPO₄²⁻ / PO₄³⁻,
+// ions which don't react with water (Na⁺) have no pKa
+#[derive(Debug, Clone, PartialEq)]
+pub struct Protolyte {
+    pub concentration: Molarity,
+    // of the form with all the H⁺
+    pub charge: i8,
+    pub pka: Vec<f64>,
+}
+
+// Everything dissolved together, every concentration is the one in the mixture
+#[derive(Debug, Clone, Default)]
+pub struct AcidBaseMixture {
+    pub protolytes: Vec<Protolyte>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hydrolysis {
+    pub by_cation: bool,
+    pub by_anion: bool,
+    pub medium: Medium,
+}
+
+impl Protolyte {
+    // fractions of the forms at the given pH, the one with all the H⁺ goes first
+    pub fn fractions(&self, ph: f64) -> Vec<f64> {
+        // lg of hⁿ⁻ⁱ·K₁…Kᵢ
+        let n = self.pka.len();
+        let mut lg = vec![-(n as f64) * ph];
+        for (i, pka) in self.pka.iter().enumerate() {
+            lg.push(lg[i] + ph - pka);
+        }
+        let max = lg.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let terms: Vec<f64> = lg.iter().map(|l| 10f64.powf(l - max)).collect();
+        let sum: f64 = terms.iter().sum();
+        terms.iter().map(|t| t / sum).collect()
+    }
+
+    // mol/L of charge it brings at the given pH
+    pub fn charge_at(&self, ph: f64) -> f64 {
+        let lost: f64 = self
+            .fractions(ph)
+            .iter()
+            .enumerate()
+            .map(|(i, x)| i as f64 * x)
+            .sum();
+        self.concentration.molarity() * (self.charge as f64 - lost)
+    }
+
+    pub fn diluted(&self, factor: f64) -> Self {
+        Self {
+            concentration: self.concentration * factor,
+            ..self.clone()
+        }
+    }
+}
+
+impl AcidBaseMixture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(
+        mut self,
+        substance: &Substance,
+        concentration: Molarity,
+        p_t: &PeriodicTable,
+    ) -> Result<Self, &'static str> {
+        self.protolytes
+            .extend(substance.protolytes(concentration, p_t)?);
+        Ok(self)
+    }
+
+    // pH where the charges are balanced: [H⁺] + Σ charges = [OH⁻]
+    pub fn ph(&self) -> f64 {
+        let balance = |ph: f64| -> f64 {
+            let h = 10f64.powf(-ph);
+            let oh = 10f64.powf(ph - PKW);
+            h - oh + self.protolytes.iter().map(|p| p.charge_at(ph)).sum::<f64>()
+        };
+        // the balance falls as pH grows
+        let (mut low, mut high) = (-3.0, PKW + 3.0);
+        for _ in 0..SOLVER_STEPS {
+            let middle = (low + high) / 2.0;
+            match balance(middle) > 0.0 {
+                true => low = middle,
+                false => high = middle,
+            }
+        }
+        (low + high) / 2.0
+    }
+
+    pub fn poh(&self) -> f64 {
+        PKW - self.ph()
+    }
+}
+
+impl Substance {
+    // Acids are taken with all their H⁺, salts and bases by their ions
+    pub fn protolytes(
+        &self,
+        concentration: Molarity,
+        p_t: &PeriodicTable,
+    ) -> Result<Vec<Protolyte>, &'static str> {
+        if concentration.molarity() < 0.0 {
+            return Err("Concentration has to be positive");
+        }
+        if self.class == SubstanceClass::Acid {
+            let acid = self.acid_properties(p_t).ok_or("Acid isn't known")?;
+            let pka = match (acid.pka.is_empty(), acid.strength) {
+                (false, _) => acid.pka,
+                (true, Strength::Strong) => vec![STRONG_PKA; acid.basicity as usize],
+                (true, Strength::Weak) => return Err("Ka of the acid is unknown"),
+            };
+            return Ok(vec![Protolyte {
+                concentration,
+                charge: 0,
+                pka,
+            }]);
+        }
+
+        self.dissociation(p_t)
+            .ok_or("Substance doesn't give ions in water")?;
+        let mut res = Vec::new();
+        for (ion, n) in self
+            .ions(p_t)
+            .ok_or("Substance doesn't give ions in water")?
+        {
+            let concentration = concentration * n as f64;
+            // OH⁻ is the one of water
+            if ion.formula(p_t) == "OH" {
+                continue;
+            }
+            let protolyte = match (weak_base(&ion, p_t), conjugate_acid(&ion, p_t)) {
+                (Some(pkb), _) => Protolyte {
+                    concentration,
+                    charge: ion.charge,
+                    pka: vec![PKW - pkb],
+                },
+                (_, Some((pka, _))) => Protolyte {
+                    concentration,
+                    charge: 0,
+                    pka: pka.to_vec(),
+                },
+                // anions of acids out of the table are taken as the ones of strong acids
+                _ => Protolyte {
+                    concentration,
+                    charge: ion.charge,
+                    pka: vec![],
+                },
+            };
+            res.push(protolyte);
+        }
+        Ok(res)
+    }
+
+    pub fn ph(&self, concentration: Molarity, p_t: &PeriodicTable) -> Result<f64, &'static str> {
+        Ok(AcidBaseMixture::new().with(self, concentration, p_t)?.ph())
+    }
+
+    pub fn poh(&self, concentration: Molarity, p_t: &PeriodicTable) -> Result<f64, &'static str> {
+        Ok(PKW - self.ph(concentration, p_t)?)
+    }
+
+    // Part of the molecules which dissociate, from 0 to 1
+    pub fn dissociation_degree(
+        &self,
+        concentration: Molarity,
+        p_t: &PeriodicTable,
+    ) -> Result<f64, &'static str> {
+        let protolytes = self.protolytes(concentration, p_t)?;
+        let ph = AcidBaseMixture {
+            protolytes: protolytes.clone(),
+        }
+        .ph();
+        match self.class {
+            SubstanceClass::Acid => Ok(1.0 - protolytes[0].fractions(ph)[0]),
+            // weak bases are the ones with the cation which takes OH⁻ back
+            SubstanceClass::Base => Ok(protolytes
+                .iter()
+                .find(|p| p.charge > 0 && !p.pka.is_empty())
+                .map_or(1.0, |p| p.fractions(ph)[0])),
+            _ => Ok(1.0),
+        }
+    }
+
+    // Salts of weak bases make the medium acidic and salts of weak acids - basic
+    pub fn hydrolysis(&self, p_t: &PeriodicTable) -> Option<Hydrolysis> {
+        if self.class != SubstanceClass::Salt {
+            return None;
+        }
+        let ions = self.ions(p_t)?;
+
+        // the strongest acid and base among the ions
+        let mut acid_pk = f64::INFINITY;
+        let mut base_pk = f64::INFINITY;
+        let (mut by_cation, mut by_anion) = (false, false);
+        for (ion, _) in ions.iter().filter(|(ion, _)| ion.formula(p_t) != "OH") {
+            if let Some(pkb) = weak_base(ion, p_t) {
+                if PKW - pkb < HYDROLYSIS_PK {
+                    acid_pk = acid_pk.min(PKW - pkb);
+                    by_cation = true;
+                }
+            }
+            // HCO₃⁻ both gives H⁺ and takes it
+            if let Some((pka, k)) = conjugate_acid(ion, p_t) {
+                let k = k as usize;
+                if let Some(pka) = pka.get(k).filter(|pka| **pka < HYDROLYSIS_PK) {
+                    acid_pk = acid_pk.min(*pka);
+                    by_anion = true;
+                }
+                if PKW - pka[k - 1] < HYDROLYSIS_PK {
+                    base_pk = base_pk.min(PKW - pka[k - 1]);
+                    by_anion = true;
+                }
+            }
+        }
+
+        let medium = match (acid_pk.is_finite(), base_pk.is_finite()) {
+            (false, false) => Medium::Neutral,
+            (true, false) => Medium::Acidic,
+            (false, true) => Medium::Basic,
+            _ if (acid_pk - base_pk).abs() < NEUTRAL_DELTA_PK => Medium::Neutral,
+            _ if acid_pk < base_pk => Medium::Acidic,
+            _ => Medium::Basic,
+        };
+        Some(Hydrolysis {
+            by_cation,
+            by_anion,
+            medium,
+        })
+    }
+}
+
+fn weak_base(cation: &Ion, p_t: &PeriodicTable) -> Option<f64> {
+    WEAK_BASES
+        .iter()
+        .find(|(formula, charge, _)| {
+            Ion::from_string(formula, *charge, p_t).is_ok_and(|ion| ion == *cation)
+        })
+        .map(|b| b.2)
+}
+
+// pKa of the acid which gives the anion and the number of H⁺ it has lost: HCO₃⁻ is H₂CO₃ - H⁺
+fn conjugate_acid(anion: &Ion, p_t: &PeriodicTable) -> Option<(&'static [f64], u8)> {
+    if anion.charge >= 0 {
+        return None;
+    }
+    let h = Ion::from_string("H", 1, p_t).ok()?;
+    let k = anion.charge.unsigned_abs();
+    ACIDS
+        .iter()
+        .filter(|(_, _, pka)| pka.len() >= k as usize)
+        .find(|(formula, _, _)| {
+            parser::collect_substance(formula, p_t)
+                .ok()
+                .and_then(|acid| subtract(&acid, &h.blocks, k))
+                .is_some_and(|rest| composition_ratio(&rest, &anion.blocks) == Some(1))
+        })
+        .map(|(_, _, pka)| (*pka, k))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solution_ph() {
+        let p_t = PeriodicTable::new();
+        let close = |a: f64, b: f64| (a - b).abs() < 0.02;
+        let substance = |formula: &str| Substance::from_string(formula, &p_t).unwrap();
+        let c = Molarity::from_molarity;
+
+        let solutions = [
+            ("HCl", 0.01, 2.0),
+            ("NaOH", 0.01, 12.0),
+            ("CH3COOH", 0.1, 2.88),
+            ("NH4OH", 0.1, 11.13),
+            ("H2SO4", 0.01, 1.85),
+            ("H3PO4", 0.1, 1.63),
+            ("Ca(OH)2", 0.01, 12.3),
+            ("CH3COONa", 0.1, 8.88),
+            ("NH4Cl", 0.1, 5.13),
+            ("NaCl", 0.1, 7.0),
+            ("NaHCO3", 0.1, 8.34),
+        ];
+        for (formula, molarity, ph) in solutions {
+            let res = substance(formula).ph(c(molarity), &p_t).unwrap();
+            assert!(close(ph, res), "{}: {}", formula, res);
+        }
+        assert!(close(2.0, substance("NaOH").poh(c(0.01), &p_t).unwrap()));
+
+        let acetic = substance("CH3COOH");
+        let degree = acetic.dissociation_degree(c(0.1), &p_t).unwrap();
+        assert!((degree - 0.0132).abs() < 0.0005);
+        assert!(acetic.dissociation_degree(c(0.001), &p_t).unwrap() > degree);
+        assert_eq!(
+            1.0,
+            substance("NaCl").dissociation_degree(c(0.1), &p_t).unwrap()
+        );
+
+        // buffer and neutralisation
+        let buffer = AcidBaseMixture::new()
+            .with(&acetic, c(0.1), &p_t)
+            .unwrap()
+            .with(&substance("CH3COONa"), c(0.1), &p_t)
+            .unwrap();
+        assert!(close(4.76, buffer.ph()));
+        let neutral = AcidBaseMixture::new()
+            .with(&substance("HCl"), c(0.01), &p_t)
+            .unwrap()
+            .with(&substance("NaOH"), c(0.01), &p_t)
+            .unwrap();
+        assert!(close(7.0, neutral.ph()) && close(7.0, neutral.poh()));
+
+        assert!(substance("CO2").ph(c(0.1), &p_t).is_err());
+        assert!(substance("BaSO4").ph(c(0.1), &p_t).is_err());
+    }
+
+    #[test]
+    fn salt_hydrolysis() {
+        let p_t = PeriodicTable::new();
+        let salts = [
+            ("NaCl", false, false, Medium::Neutral),
+            ("Na2SO4", false, false, Medium::Neutral),
+            ("Na2CO3", false, true, Medium::Basic),
+            ("NaHCO3", false, true, Medium::Basic),
+            ("NaH2PO4", false, true, Medium::Acidic),
+            ("AlCl3", true, false, Medium::Acidic),
+            ("NH4NO3", true, false, Medium::Acidic),
+            ("CH3COONH4", true, true, Medium::Neutral),
+            ("(NH4)2CO3", true, true, Medium::Basic),
+        ];
+        for (formula, by_cation, by_anion, medium) in salts {
+            let hydrolysis = Substance::from_string(formula, &p_t)
+                .unwrap()
+                .hydrolysis(&p_t)
+                .unwrap();
+            assert_eq!(
+                Hydrolysis {
+                    by_cation,
+                    by_anion,
+                    medium
+                },
+                hydrolysis,
+                "{}",
+                formula
+            );
+        }
+        let hcl = Substance::from_string("HCl", &p_t).unwrap();
+        assert!(hcl.hydrolysis(&p_t).is_none());
+    }
+}
@@ -0,0 +1,135 @@
+use super::{
+    ion::Ion,
+    substance::{is_composition, Substance, SubstanceClass},
+};
+use crate::utils::{
+    periodic_table::PeriodicTable,
+    solubility_table::{CATIONS, GASES, SOLUBILITY_G_PER_100G, SOLUBILITY_TABLE},
+};
+
+// from the most to the least suitable for solution
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub enum Solubility {
+    Soluble,
+    SlightlySoluble,
+    Insoluble,
+    Decomposes,
+    NotExists,
+}
+
+impl Solubility {
+    fn from_letter(c: char) -> Option<Self> {
+        match c {
+            'R' => Some(Self::Soluble),
+            'M' => Some(Self::SlightlySoluble),
+            'N' => Some(Self::Insoluble),
+            'D' => Some(Self::Decomposes),
+            'X' => Some(Self::NotExists),
+            _ => None,
+        }
+    }
+}
+
+// cation x anion cell of the table
+pub fn solubility_of(cation: &Ion, anion: &Ion, p_t: &PeriodicTable) -> Option<Solubility> {
+    let (cation_f, anion_f) = (cation.formula(p_t), anion.formula(p_t));
+    let column = CATIONS
+        .iter()
+        .position(|c| c.0 == cation_f && c.1 == cation.charge)?;
+    let row = SOLUBILITY_TABLE
+        .iter()
+        .find(|a| a.0 == anion_f && a.1 == anion.charge)?;
+    Solubility::from_letter(row.2.chars().nth(column)?)
+}
+
+impl Substance {
+    // the worst of cation x anion pairs,
+    // OH⁻ of basic salts doesn't count - it's the other anion which matters
+    pub fn solubility(&self, p_t: &PeriodicTable) -> Option<Solubility> {
+        let ions = self.ions(p_t)?;
+        let is_oh = |ion: &Ion| ion.formula(p_t) == "OH";
+        let basic_salt = self.class == SubstanceClass::Salt
+            && ions.iter().any(|(ion, _)| is_oh(ion))
+            && ions.iter().any(|(ion, _)| ion.charge < 0 && !is_oh(ion));
+
+        let mut res = None;
+        for (cation, _) in ions.iter().filter(|(ion, _)| ion.charge > 0) {
+            for (anion, _) in ions.iter().filter(|(ion, _)| ion.charge < 0) {
+                if basic_salt && is_oh(anion) {
+                    continue;
+                }
+                if let Some(s) = solubility_of(cation, anion, p_t) {
+                    res = match res {
+                        Some(r) if r >= s => Some(r),
+                        _ => Some(s),
+                    };
+                }
+            }
+        }
+        res
+    }
+
+    // g per 100 g of water at 20°C, for common compounds only
+    pub fn solubility_in_water(&self, p_t: &PeriodicTable) -> Option<f64> {
+        let sbs = self.blocks();
+        SOLUBILITY_G_PER_100G
+            .iter()
+            .find(|(formula, _)| is_composition(&sbs, formula, p_t))
+            .map(|(_, s)| *s)
+    }
+
+    pub fn is_gas(&self, p_t: &PeriodicTable) -> bool {
+        let sbs = self.blocks();
+        GASES
+            .iter()
+            .any(|formula| is_composition(&sbs, formula, p_t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substance_solubility() {
+        let p_t = PeriodicTable::new();
+        let substances = [
+            ("NaCl", Solubility::Soluble),
+            ("H2SO4", Solubility::Soluble),
+            ("NH4Cl", Solubility::Soluble),
+            ("Ca(OH)2", Solubility::SlightlySoluble),
+            ("BaSO4", Solubility::Insoluble),
+            ("AgCl", Solubility::Insoluble),
+            ("Cu(OH)2", Solubility::Insoluble),
+            ("H2SiO3", Solubility::Insoluble),
+            ("Al(OH)2Cl", Solubility::Soluble),
+            ("Al2S3", Solubility::Decomposes),
+            ("FeI3", Solubility::NotExists),
+        ];
+
+        for (formula, solubility) in substances {
+            let s = Substance::from_string(formula, &p_t).unwrap();
+            assert_eq!(Some(solubility), s.solubility(&p_t));
+        }
+
+        let co2 = Substance::from_string("CO2", &p_t).unwrap();
+        assert_eq!(None, co2.solubility(&p_t));
+    }
+
+    #[test]
+    fn substance_solubility_in_water() {
+        let p_t = PeriodicTable::new();
+
+        let nacl = Substance::from_string("NaCl", &p_t).unwrap();
+        assert_eq!(Some(35.9), nacl.solubility_in_water(&p_t));
+        let caco3 = Substance::from_string("CaCO3", &p_t).unwrap();
+        assert!(caco3.solubility_in_water(&p_t).unwrap() < 0.01);
+        let cs2so4 = Substance::from_string("Cs2SO4", &p_t).unwrap();
+        assert_eq!(None, cs2so4.solubility_in_water(&p_t));
+
+        for (formula, gas) in [("CO2", true), ("H2", true), ("HCl", false), ("H2O", false)] {
+            let s = Substance::from_string(formula, &p_t).unwrap();
+            assert_eq!(gas, s.is_gas(&p_t), "{formula}");
+        }
+    }
+}
@@ -0,0 +1,342 @@
+use super::substance::{composition_ratio, Substance};
+use crate::{
+    quantity::{Amount, Density, Mass, Molality, MolarMass, Molarity, Volume},
+    utils::periodic_table::PeriodicTable,
+};
+
+#[derive(Debug, Clone)]
+pub struct Solution {
+    pub solute: Substance,
+    pub solvent: Substance,
+    pub solute_mass: Mass,
+    pub solvent_mass: Mass,
+    // of the whole solution, it's needed for the volume
+    pub density: Option<Density>,
+}
+
+// Crystals with water: CuSO₄·5H₂O
+#[derive(Debug, Clone)]
+pub struct Hydrate {
+    pub salt: Substance,
+    pub water: u8,
+}
+
+impl Substance {
+    // Charge which 1 mol of it gives or takes in acid-base reactions: 2 for H₂SO₄ and Ca(OH)₂
+    pub fn equivalence_factor(&self, p_t: &PeriodicTable) -> Option<u8> {
+        let ions = self.ions(p_t)?;
+        let charge: u8 = ions
+            .iter()
+            .filter(|(ion, _)| ion.charge > 0)
+            .map(|(ion, n)| ion.charge as u8 * n)
+            .sum();
+        (charge > 0).then_some(charge)
+    }
+}
+
+impl Solution {
+    pub fn new(
+        solute: Substance,
+        solvent: Substance,
+        solute_mass: Mass,
+        solvent_mass: Mass,
+    ) -> Result<Self, &'static str> {
+        if solute_mass.grams() < 0.0 || solvent_mass.grams() <= 0.0 {
+            return Err("Masses of the solution have to be positive");
+        }
+        Ok(Self {
+            solute,
+            solvent,
+            solute_mass,
+            solvent_mass,
+            density: None,
+        })
+    }
+
+    pub fn with_density(mut self, density: Density) -> Self {
+        self.density = Some(density);
+        self
+    }
+
+    // w from 0 to 1 of the solution of the given mass
+    pub fn from_mass_fraction(
+        solute: Substance,
+        solvent: Substance,
+        w: f64,
+        mass: Mass,
+    ) -> Result<Self, &'static str> {
+        if !(0.0..1.0).contains(&w) {
+            return Err("Mass fraction has to be from 0 to 1");
+        }
+        Self::new(solute, solvent, mass * w, mass * (1.0 - w))
+    }
+
+    pub fn from_molarity(
+        solute: Substance,
+        solvent: Substance,
+        concentration: Molarity,
+        volume: Volume,
+        density: Density,
+    ) -> Result<Self, &'static str> {
+        let solute_mass = volume * concentration * solute.molar_mass();
+        let solvent_mass = volume * density - solute_mass;
+        Ok(Self::new(solute, solvent, solute_mass, solvent_mass)?.with_density(density))
+    }
+
+    pub fn from_molality(
+        solute: Substance,
+        solvent: Substance,
+        molality: Molality,
+        solvent_mass: Mass,
+    ) -> Result<Self, &'static str> {
+        let moles = molality.moles_per_kilogram() * solvent_mass.to("kg")?;
+        let solute_mass = Amount::from_moles(moles) * solute.molar_mass();
+        Self::new(solute, solvent, solute_mass, solvent_mass)
+    }
+
+    // x from 0 to 1 of the solution of the given mass
+    pub fn from_mole_fraction(
+        solute: Substance,
+        solvent: Substance,
+        x: f64,
+        mass: Mass,
+    ) -> Result<Self, &'static str> {
+        if !(0.0..1.0).contains(&x) {
+            return Err("Mole fraction has to be from 0 to 1");
+        }
+        // x mol of solute with 1 - x mol of solvent
+        let solute_part = solute.molar_mass().grams_per_mole() * x;
+        let solvent_part = solvent.molar_mass().grams_per_mole() * (1.0 - x);
+        let w = solute_part / (solute_part + solvent_part);
+        Self::from_mass_fraction(solute, solvent, w, mass)
+    }
+
+    pub fn mass(&self) -> Mass {
+        self.solute_mass + self.solvent_mass
+    }
+
+    pub fn amount(&self) -> Amount {
+        self.solute_mass / self.solute.molar_mass()
+    }
+
+    pub fn volume(&self) -> Result<Volume, &'static str> {
+        let density = self.density.ok_or("Density of the solution is required")?;
+        Ok(self.mass() / density)
+    }
+
+    pub fn mass_fraction(&self) -> f64 {
+        self.solute_mass / self.mass()
+    }
+
+    // by mass, mg of solute in 1 kg of the solution
+    pub fn ppm(&self) -> f64 {
+        self.mass_fraction() * 1e6
+    }
+
+    // g of solute in 1 L of the solution, mg/L are ppm of water analysis
+    pub fn mass_concentration(&self) -> Result<Density, &'static str> {
+        Ok(self.solute_mass / self.volume()?)
+    }
+
+    pub fn mole_fraction(&self) -> f64 {
+        let solvent = self.solvent_mass / self.solvent.molar_mass();
+        self.amount() / (self.amount() + solvent)
+    }
+
+    pub fn molarity(&self) -> Result<Molarity, &'static str> {
+        Ok(self.amount() / self.volume()?)
+    }
+
+    // mol per kg of the solvent
+    pub fn molality(&self) -> Result<Molality, &'static str> {
+        Ok(Molality::from_moles_per_kilogram(
+            self.amount().moles() / self.solvent_mass.to("kg")?,
+        ))
+    }
+
+    // equivalents in 1 L: molarity × equivalence factor
+    pub fn normality(&self, p_t: &PeriodicTable) -> Result<Molarity, &'static str> {
+        let z = self
+            .solute
+            .equivalence_factor(p_t)
+            .ok_or("Solute isn't an acid, base or salt")?;
+        Ok(self.molarity()? * z as f64)
+    }
+
+    // The same solute in more solvent, the density isn't known anymore
+    pub fn dilute(&self, solvent: Mass) -> Self {
+        Self {
+            solvent_mass: self.solvent_mass + solvent,
+            density: None,
+            ..self.clone()
+        }
+    }
+
+    // C₁V₁ = C₂V₂: the volume to make of the solution
+    pub fn dilution_volume(&self, target: Molarity) -> Result<Volume, &'static str> {
+        if target.molarity() <= 0.0 {
+            return Err("Concentration has to be positive");
+        }
+        if target > self.molarity()? {
+            return Err("Dilution can't make the solution stronger");
+        }
+        Ok(self.amount() / target)
+    }
+
+    pub fn mix(&self, other: &Solution) -> Result<Self, &'static str> {
+        let same =
+            |a: &Substance, b: &Substance| composition_ratio(&a.blocks(), &b.blocks()) == Some(1);
+        if !same(&self.solute, &other.solute) || !same(&self.solvent, &other.solvent) {
+            return Err("Only solutions of the same solute in the same solvent are mixed");
+        }
+        Ok(Self {
+            solute_mass: self.solute_mass + other.solute_mass,
+            solvent_mass: self.solvent_mass + other.solvent_mass,
+            density: None,
+            ..self.clone()
+        })
+    }
+}
+
+impl Hydrate {
+    // "CuSO4·5H2O", "*" may be written instead of "·"
+    pub fn from_string(s: &str, p_t: &PeriodicTable) -> Result<Self, &'static str> {
+        let (salt, water) = s
+            .split_once(['·', '*'])
+            .ok_or("Hydrate is written as salt·nH2O")?;
+        let digits = water.find(|c: char| !c.is_ascii_digit()).unwrap_or(0);
+        let n = match digits {
+            0 => 1,
+            _ => water[..digits]
+                .parse()
+                .map_err(|_| "Wrong number of water")?,
+        };
+        let h2o = Substance::from_string("H2O", p_t)?;
+        let water = Substance::from_string(&water[digits..], p_t)?;
+        if composition_ratio(&water.blocks(), &h2o.blocks()) != Some(1) {
+            return Err("Hydrate is written as salt·nH2O");
+        }
+        Ok(Self {
+            salt: Substance::from_string(salt, p_t)?,
+            water: n,
+        })
+    }
+
+    pub fn molar_mass(&self, p_t: &PeriodicTable) -> Result<MolarMass, &'static str> {
+        let h2o = Substance::from_string("H2O", p_t)?;
+        Ok(self.salt.molar_mass() + h2o.molar_mass() * self.water as f64)
+    }
+
+    // how much of crystals to weigh for the volume of the solution:
+    // 1 mol of hydrate gives 1 mol of the salt
+    pub fn mass_for(
+        &self,
+        volume: Volume,
+        concentration: Molarity,
+        p_t: &PeriodicTable,
+    ) -> Result<Mass, &'static str> {
+        Ok(volume * concentration * self.molar_mass(p_t)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solution() {
+        let p_t = PeriodicTable::new();
+        let close = |a: f64, b: f64| (a - b).abs() < 0.01;
+        let substance = |formula: &str| Substance::from_string(formula, &p_t).unwrap();
+
+        // 10% NaOH of 1.109 g/mL
+        let naoh = Solution::from_mass_fraction(
+            substance("NaOH"),
+            substance("H2O"),
+            0.1,
+            Mass::from_grams(200.0),
+        )
+        .unwrap();
+        assert!(naoh.molarity().is_err());
+        let naoh = naoh.with_density("1.109 g/mL".parse().unwrap());
+        assert!(close(180.34, naoh.volume().unwrap().to("mL").unwrap()));
+        assert!(close(2.77, naoh.molarity().unwrap().molarity()));
+        assert!(close(2.78, naoh.molality().unwrap().moles_per_kilogram()));
+        assert!(close(0.0476, naoh.mole_fraction()));
+        assert!(close(100_000.0, naoh.ppm()));
+        let mg_per_litre = naoh.mass_concentration().unwrap().to("mg/L").unwrap();
+        assert!(close(110_900.0, mg_per_litre));
+
+        // 1 M H₂SO₄ is 2 N
+        let h2so4 = Solution::from_molarity(
+            substance("H2SO4"),
+            substance("H2O"),
+            Molarity::from_molarity(1.0),
+            Volume::from_litres(1.0),
+            "1.06 g/mL".parse().unwrap(),
+        )
+        .unwrap();
+        assert!(close(98.08, h2so4.solute_mass.grams()));
+        assert!(close(2.0, h2so4.normality(&p_t).unwrap().molarity()));
+
+        let molal = Solution::from_molality(
+            substance("NaCl"),
+            substance("H2O"),
+            Molality::from_moles_per_kilogram(1.0),
+            Mass::new(1.0, "kg").unwrap(),
+        )
+        .unwrap();
+        assert!(close(58.44, molal.solute_mass.grams()));
+        let by_x = Solution::from_mole_fraction(
+            substance("NaCl"),
+            substance("H2O"),
+            molal.mole_fraction(),
+            molal.mass(),
+        )
+        .unwrap();
+        assert!(close(58.44, by_x.solute_mass.grams()));
+    }
+
+    #[test]
+    fn solution_dilution() {
+        let p_t = PeriodicTable::new();
+        let close = |a: f64, b: f64| (a - b).abs() < 0.01;
+        let substance = |formula: &str| Substance::from_string(formula, &p_t).unwrap();
+
+        let nacl = |w: f64, mass: f64| {
+            Solution::from_mass_fraction(
+                substance("NaCl"),
+                substance("H2O"),
+                w,
+                Mass::from_grams(mass),
+            )
+            .unwrap()
+        };
+        let diluted = nacl(0.2, 100.0).dilute(Mass::from_grams(100.0));
+        assert!(close(0.1, diluted.mass_fraction()));
+        let mixed = nacl(0.2, 100.0).mix(&nacl(0.05, 300.0)).unwrap();
+        assert!(close(0.0875, mixed.mass_fraction()));
+        let soda = Solution::from_mass_fraction(
+            substance("CO2"),
+            substance("H2O"),
+            0.01,
+            Mass::from_grams(100.0),
+        )
+        .unwrap();
+        assert!(nacl(0.2, 100.0).mix(&soda).is_err());
+
+        let strong = nacl(0.2, 100.0).with_density("1.148 g/mL".parse().unwrap());
+        let volume = strong.dilution_volume("0.5 M".parse().unwrap()).unwrap();
+        assert!(close(0.684, volume.litres()));
+        assert!(strong.dilution_volume(Molarity::default()).is_err());
+
+        // 250 mL of 0.1 M CuSO₄ from blue vitriol
+        let vitriol = Hydrate::from_string("CuSO4·5H2O", &p_t).unwrap();
+        assert_eq!(5, vitriol.water);
+        let mass = vitriol
+            .mass_for("250 mL".parse().unwrap(), "0.1 M".parse().unwrap(), &p_t)
+            .unwrap();
+        assert!(close(6.24, mass.grams()));
+        assert!(Hydrate::from_string("CuSO4·5CO2", &p_t).is_err());
+    }
+}
@@ -1,8 +1,11 @@
-use super::element::Element;
+use super::{concentration::Concentration, element::Element, ion::ammonium_ions};
 use std::collections::HashMap;
 use std::fmt;
 
-use crate::utils::{parser, periodic_table::PeriodicTable};
+use crate::{
+    quantity::MolarMass,
+    utils::{acid_table::ACIDS, ion_table::IONS, parser, periodic_table::PeriodicTable},
+};
 
 #[derive(Debug, PartialEq, Clone, Copy, Hash, Eq)]
 pub enum SubstanceClass {
@@ -13,6 +16,8 @@ pub enum SubstanceClass {
     Base,
     Acid,
     Salt,
+    // non-metals with each other: PCl₅, CS₂, CH₄
+    Binary,
 }
 
 #[derive(Debug, Clone)]
@@ -41,6 +46,8 @@ pub struct Substance {
     pub me: HashMap<String, SubstanceBlock>,
     pub anti_me: HashMap<String, SubstanceBlock>,
     pub class: SubstanceClass,
+    // only for solutions, None - as the reaction usually needs it
+    pub concentration: Option<Concentration>,
 }
 
 impl PartialEq for Substance {
@@ -67,41 +74,43 @@ impl PartialEq for Substance {
 
 impl fmt::Display for Substance {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut res = String::new();
-        for group in [&self.me, &self.anti_me] {
-            for (name, sb) in group {
-                res = format!("{}{}{}", res, name, sb.index);
-            }
-        }
-        write!(f, "{}", res)
+        let p_t = PeriodicTable::new();
+        write!(f, "{}", self.formula(&p_t))
     }
 }
 
 // Not one great distrubutor, but many small - one for every SubstanceClass
 impl Substance {
     pub fn from_string(s: &str, p_t: &PeriodicTable) -> Result<Self, &'static str> {
-        let mut e = parser::collect_elements(s, p_t)?;
-        if e.len() != 1 {
-            return Err(&"There's must be only one substance");
-        }
-        Self::from_elements(e.swap_remove(0))
+        Self::from_elements(parser::collect_substance(s, p_t)?, p_t)
     }
-    pub fn from_elements(sb: HashMap<String, SubstanceBlock>) -> Result<Self, &'static str> {
+    pub fn from_elements(
+        sb: HashMap<String, SubstanceBlock>,
+        p_t: &PeriodicTable,
+    ) -> Result<Self, &'static str> {
         let checkers: Vec<
-            fn(HashMap<String, SubstanceBlock>) -> Result<Self, HashMap<String, SubstanceBlock>>,
+            fn(
+                HashMap<String, SubstanceBlock>,
+                &PeriodicTable,
+            ) -> Result<Self, HashMap<String, SubstanceBlock>>,
         > = vec![
             Self::try_hydride,
             Self::try_peroxide,
             Self::try_oxide,
             Self::try_base,
+            // H₂CrO₄ and HMnO₄ have Me, but they are acids
+            Self::try_known_acid,
+            Self::try_ammonium_salt,
             Self::try_salt,
             Self::try_acid,
+            Self::try_known_salt,
+            Self::try_binary,
         ];
         let mut res = Self::try_simple(sb);
         for checker in checkers {
             res = match res {
                 Ok(s) => return Ok(s),
-                Err(sb) => checker(sb),
+                Err(sb) => checker(sb, p_t),
             };
         }
 
@@ -111,6 +120,22 @@ impl Substance {
         }
     }
 
+    // all SubstanceBlocks together - me and anti_me
+    pub fn blocks(&self) -> HashMap<String, SubstanceBlock> {
+        let mut sbs = self.me.clone();
+        sbs.extend(self.anti_me.clone());
+        sbs
+    }
+
+    pub fn molar_mass(&self) -> MolarMass {
+        MolarMass::from_grams_per_mole(
+            self.blocks()
+                .values()
+                .map(|sb| sb.element.a_rm * sb.index as f64)
+                .sum(),
+        )
+    }
+
     fn try_simple(
         sbs: HashMap<String, SubstanceBlock>,
     ) -> Result<Self, HashMap<String, SubstanceBlock>> {
@@ -125,17 +150,20 @@ impl Substance {
                 me: sbs,
                 anti_me: HashMap::new(),
                 class: SubstanceClass::Simple,
+                concentration: None,
             }),
             _ => Ok(Self {
                 me: HashMap::new(),
                 anti_me: sbs,
                 class: SubstanceClass::Simple,
+                concentration: None,
             }),
         }
     }
 
     fn try_hydride(
         mut sbs: HashMap<String, SubstanceBlock>,
+        _p_t: &PeriodicTable,
     ) -> Result<Self, HashMap<String, SubstanceBlock>> {
         if sbs.len() != 2 {
             return Err(sbs);
@@ -182,11 +210,13 @@ impl Substance {
             me,
             anti_me,
             class: SubstanceClass::Hydride,
+            concentration: None,
         })
     }
 
     fn try_oxide(
         mut sbs: HashMap<String, SubstanceBlock>,
+        _p_t: &PeriodicTable,
     ) -> Result<Self, HashMap<String, SubstanceBlock>> {
         if sbs.len() != 2 {
             return Err(sbs);
@@ -226,11 +256,13 @@ impl Substance {
             me,
             anti_me,
             class: SubstanceClass::Oxide,
+            concentration: None,
         })
     }
 
     fn try_peroxide(
         mut sbs: HashMap<String, SubstanceBlock>,
+        _p_t: &PeriodicTable,
     ) -> Result<Self, HashMap<String, SubstanceBlock>> {
         if sbs.len() != 2 {
             return Err(sbs);
@@ -242,9 +274,9 @@ impl Substance {
         };
 
         let mut sb = sbs.drain().next().unwrap();
-        // peroxides - it's for active Me only(exclude Be)
+        // peroxides - it's for active Me and H only(exclude Be)
         if sb.1.element.group > 2
-            || sb.1.element.group == sb.1.element.period
+            || (sb.1.element.group == 2 && sb.1.element.period == 2)
             || o2.1.index != 2
             || f64::from(sb.1.element.valencies[0]) * f64::from(sb.1.index) != 2.0
         {
@@ -281,11 +313,13 @@ impl Substance {
             me,
             anti_me,
             class: SubstanceClass::Peroxide,
+            concentration: None,
         })
     }
 
     fn try_base(
         mut sbs: HashMap<String, SubstanceBlock>,
+        _p_t: &PeriodicTable,
     ) -> Result<Self, HashMap<String, SubstanceBlock>> {
         // only hydroxides - inorganic bases
         if sbs.len() != 3 {
@@ -316,6 +350,7 @@ impl Substance {
                 me: HashMap::new(),
                 anti_me: HashMap::from([sb, o, h]),
                 class: SubstanceClass::Base,
+                concentration: None,
             });
         }
 
@@ -342,6 +377,7 @@ impl Substance {
                 me: HashMap::from([sb]),
                 anti_me: HashMap::from([o, h]),
                 class: SubstanceClass::Base,
+                concentration: None,
             });
         }
 
@@ -353,6 +389,7 @@ impl Substance {
 
     fn try_acid(
         mut sbs: HashMap<String, SubstanceBlock>,
+        _p_t: &PeriodicTable,
     ) -> Result<Self, HashMap<String, SubstanceBlock>> {
         let mut h = match sbs.remove_entry("H") {
             Some(el) => el,
@@ -363,10 +400,10 @@ impl Substance {
         // is there something with big electronegativity - e.g. O or S
         let mut ox_eln = 0_f32;
         let mut oxidant = String::new();
+        if sbs.values().any(|sb| sb.element.group < 3) {
+            return wrong_class(vec![sbs], vec![Some(h)]);
+        }
         for sb in &mut sbs {
-            if sb.1.element.group < 3 {
-                return wrong_class(vec![], vec![Some(h)]);
-            }
             if (sb.1.element.group > 15 || sb.1.element.electronegativity > 2.8)
                 && ox_eln < sb.1.element.electronegativity
             {
@@ -376,7 +413,7 @@ impl Substance {
         }
         let mut ox = match oxidant.is_empty() {
             false => sbs.remove_entry(&oxidant).unwrap(),
-            _ => return wrong_class(vec![], vec![Some(h)]),
+            _ => return wrong_class(vec![sbs], vec![Some(h)]),
         };
         ox.1.oxidation_state = ox.1.element.group as i8 - 18;
 
@@ -385,7 +422,7 @@ impl Substance {
 
         match res_vals.iter().position(|&x| -x == h.1.index as i16) {
             Some(i) => valencies_by_variant(&mut sbs, i, len),
-            _ => return wrong_class(vec![], vec![Some(h), Some(ox)]),
+            _ => return wrong_class(vec![sbs], vec![Some(h), Some(ox)]),
         }
 
         let mut me = HashMap::new();
@@ -401,11 +438,44 @@ impl Substance {
             me,
             anti_me,
             class: SubstanceClass::Acid,
+            concentration: None,
+        })
+    }
+
+    // NH₄⁺ instead of Me, e.g. NH₄Cl or (NH₄)₂SO₄
+    fn try_ammonium_salt(
+        sbs: HashMap<String, SubstanceBlock>,
+        p_t: &PeriodicTable,
+    ) -> Result<Self, HashMap<String, SubstanceBlock>> {
+        let ions = match ammonium_ions(&sbs, p_t) {
+            Some(ions) => ions,
+            None => return Err(sbs),
+        };
+
+        // N of NH₄NO₃ gets the average oxidation state of NH₄⁺ and NO₃⁻
+        let mut totals = HashMap::<String, i16>::new();
+        for (ion, n) in &ions {
+            for (name, sb) in &ion.blocks {
+                *totals.entry(name.clone()).or_insert(0) +=
+                    sb.oxidation_state as i16 * (sb.index * n) as i16;
+            }
+        }
+        let mut anti_me = sbs;
+        for (name, sb) in anti_me.iter_mut() {
+            sb.oxidation_state = (totals[name] / sb.index as i16) as i8;
+        }
+
+        Ok(Self {
+            me: HashMap::new(),
+            anti_me,
+            class: SubstanceClass::Salt,
+            concentration: None,
         })
     }
 
     fn try_salt(
         mut sbs: HashMap<String, SubstanceBlock>,
+        _p_t: &PeriodicTable,
     ) -> Result<Self, HashMap<String, SubstanceBlock>> {
         let mut me = HashMap::new();
         let mut anti_me = HashMap::new();
@@ -447,7 +517,8 @@ impl Substance {
             },
         };
         ox.1.oxidation_state = (ox.1.element.group as i8 - 18) * ox.1.index as i8;
-        if anti_me.len() == 0 {
+        // the only Me of binary salt (CuCl₂) is a cation itself
+        if anti_me.len() == 0 && me.len() > 1 {
             if !importants[1].is_empty() {
                 let m = me.remove_entry(&importants[1]).unwrap();
                 anti_me.insert(m.0, m.1);
@@ -491,6 +562,7 @@ impl Substance {
                                 me,
                                 anti_me,
                                 class: SubstanceClass::Salt,
+                                concentration: None,
                             });
                         }
                     }
@@ -510,6 +582,8 @@ impl Substance {
                 if mes_valency_variants[i] == -res_valency_variants[j] {
                     valencies_by_variant(&mut me, i, me_len);
                     valencies_by_variant(&mut anti_me, j, res_len);
+                    // it was the state of all oxidant atoms together
+                    ox.1.oxidation_state = ox.1.element.group as i8 - 18;
                     anti_me.insert(ox.0, ox.1);
                     if let Some(h) = h_save {
                         anti_me.insert(h.0, h.1);
@@ -518,6 +592,7 @@ impl Substance {
                         me,
                         anti_me,
                         class: SubstanceClass::Salt,
+                        concentration: None,
                     });
                 }
             }
@@ -525,6 +600,208 @@ impl Substance {
 
         wrong_class(vec![me, anti_me], vec![h_save, Some(ox)])
     }
+
+    // acids from the table, which oxidation states
+    // can't be guessed by valencies (H₃PO₂, CH₃COOH)
+    fn try_known_acid(
+        mut sbs: HashMap<String, SubstanceBlock>,
+        p_t: &PeriodicTable,
+    ) -> Result<Self, HashMap<String, SubstanceBlock>> {
+        let is_known = ACIDS
+            .iter()
+            .any(|(formula, _, _)| is_composition(&sbs, formula, p_t));
+        if !is_known || !balance_oxidation_states(&mut sbs, 0) {
+            return Err(sbs);
+        }
+
+        let mut me = HashMap::new();
+        let mut anti_me = HashMap::new();
+        for sb in sbs {
+            match sb.1.element.is_me() && sb.0 != "H" {
+                true => me.insert(sb.0, sb.1),
+                false => anti_me.insert(sb.0, sb.1),
+            };
+        }
+
+        Ok(Self {
+            me,
+            anti_me,
+            class: SubstanceClass::Acid,
+            concentration: None,
+        })
+    }
+
+    // Me + named anion, e.g. (CH₃COO)₂Zn
+    fn try_known_salt(
+        sbs: HashMap<String, SubstanceBlock>,
+        p_t: &PeriodicTable,
+    ) -> Result<Self, HashMap<String, SubstanceBlock>> {
+        let mut me = HashMap::new();
+        let mut anti_me = HashMap::new();
+        for sb in sbs {
+            match sb.1.element.is_me() && sb.0 != "H" {
+                true => me.insert(sb.0, sb.1),
+                false => anti_me.insert(sb.0, sb.1),
+            };
+        }
+        if me.len() != 1 {
+            return wrong_class(vec![me, anti_me], vec![]);
+        }
+
+        let m = me.values_mut().next().unwrap();
+        for (formula, charge, _) in IONS.iter().filter(|ion| ion.1 < 0) {
+            let ion = match parser::collect_substance(formula, p_t) {
+                Ok(ion) => ion,
+                Err(_) => continue,
+            };
+            let k = match composition_ratio(&anti_me, &ion) {
+                Some(k) => k,
+                None => continue,
+            };
+
+            let total = -(*charge as i16) * k as i16;
+            let oxy = total / m.index as i16;
+            if total % m.index as i16 != 0 || !m.element.valencies.contains(&(oxy as u8)) {
+                continue;
+            }
+            if balance_oxidation_states(&mut anti_me, (-total) as i8) {
+                m.oxidation_state = oxy as i8;
+                return Ok(Self {
+                    me,
+                    anti_me,
+                    class: SubstanceClass::Salt,
+                    concentration: None,
+                });
+            }
+        }
+
+        wrong_class(vec![me, anti_me], vec![])
+    }
+
+    // the more electronegative non-metal takes its lowest state,
+    // the other one takes the rest: PCl₅, SF₆, CH₄
+    fn try_binary(
+        mut sbs: HashMap<String, SubstanceBlock>,
+        _p_t: &PeriodicTable,
+    ) -> Result<Self, HashMap<String, SubstanceBlock>> {
+        let non_me = sbs
+            .iter()
+            .all(|(name, sb)| !sb.element.is_me() || name == "H");
+        if sbs.len() != 2 || !non_me {
+            return Err(sbs);
+        }
+
+        let mut names: Vec<String> = sbs.keys().cloned().collect();
+        names.sort_by(|a, b| {
+            let a = sbs[a].element.electronegativity;
+            let b = sbs[b].element.electronegativity;
+            a.total_cmp(&b)
+        });
+        let (pos, neg) = (&names[0], &names[1]);
+
+        let neg_oxy = match neg.as_str() {
+            "H" => -1,
+            _ => sbs[neg].element.group as i8 - 18,
+        };
+        let total = -(neg_oxy as i16) * sbs[neg].index as i16;
+        let pos_idx = sbs[pos].index as i16;
+        let oxy = total / pos_idx;
+        if total % pos_idx != 0 || !sbs[pos].element.valencies.contains(&(oxy as u8)) {
+            return Err(sbs);
+        }
+        sbs.get_mut(neg).unwrap().oxidation_state = neg_oxy;
+        sbs.get_mut(pos).unwrap().oxidation_state = oxy as i8;
+
+        let mut me = HashMap::new();
+        let mut anti_me = HashMap::new();
+        for sb in sbs {
+            match sb.1.element.is_me() {
+                true => me.insert(sb.0, sb.1),
+                false => anti_me.insert(sb.0, sb.1),
+            };
+        }
+
+        Ok(Self {
+            me,
+            anti_me,
+            class: SubstanceClass::Binary,
+            concentration: None,
+        })
+    }
+}
+
+// k when sbs consist of exactly k parts, e.g. S₃O₁₂ = 3 * SO₄
+pub fn composition_ratio(
+    sbs: &HashMap<String, SubstanceBlock>,
+    part: &HashMap<String, SubstanceBlock>,
+) -> Option<u8> {
+    if sbs.len() != part.len() || part.is_empty() {
+        return None;
+    }
+
+    let mut k = None;
+    for (name, p) in part {
+        let sb = sbs.get(name)?;
+        if p.index == 0
+            || sb.index % p.index != 0
+            || *k.get_or_insert(sb.index / p.index) != sb.index / p.index
+        {
+            return None;
+        }
+    }
+    k
+}
+
+// sbs is exactly the formula, e.g. the one from a table
+pub fn is_composition(
+    sbs: &HashMap<String, SubstanceBlock>,
+    formula: &str,
+    p_t: &PeriodicTable,
+) -> bool {
+    parser::collect_substance(formula, p_t).is_ok_and(|f| composition_ratio(sbs, &f) == Some(1))
+}
+
+// H is +1 and O is -2, without O the most electronegative element
+// takes its lowest state and the only one left takes the rest of charge
+pub fn balance_oxidation_states(sbs: &mut HashMap<String, SubstanceBlock>, charge: i8) -> bool {
+    let mut rest = charge as i16;
+    let mut unknown = Vec::new();
+    for (name, sb) in sbs.iter_mut() {
+        match name.as_str() {
+            "H" => sb.oxidation_state = 1,
+            "O" => sb.oxidation_state = -2,
+            _ => {
+                unknown.push(name.clone());
+                continue;
+            }
+        }
+        rest -= sb.oxidation_state as i16 * sb.index as i16;
+    }
+
+    if unknown.len() == 2 && !sbs.contains_key("O") {
+        unknown.sort_by(|a, b| {
+            sbs[b]
+                .element
+                .electronegativity
+                .total_cmp(&sbs[a].element.electronegativity)
+        });
+        let ox = sbs.get_mut(&unknown.remove(0)).unwrap();
+        ox.oxidation_state = ox.element.group as i8 - 18;
+        rest -= ox.oxidation_state as i16 * ox.index as i16;
+    }
+
+    match unknown.pop() {
+        Some(name) if unknown.is_empty() => {
+            let sb = sbs.get_mut(&name).unwrap();
+            if rest % sb.index as i16 != 0 {
+                return false;
+            }
+            sb.oxidation_state = (rest / sb.index as i16) as i8;
+            true
+        }
+        Some(_) => false,
+        None => rest == 0,
+    }
 }
 
 fn wrong_class(
@@ -567,7 +844,7 @@ fn valency_variants(sbs: &HashMap<String, SubstanceBlock>, start: i16) -> (Vec<i
         for i in 0..l / len {
             for v_i in 0..val_n {
                 for j in 0..len / val_n {
-                    let idx = i * l / len + v_i * len / val_n + j;
+                    let idx = i * len + v_i * len / val_n + j;
                     variants[idx] += (sb.element.valencies[v_i] * sb.index) as i16;
                 }
             }
@@ -631,8 +908,8 @@ mod tests {
 
     #[test]
     fn substance_peroxide() {
-        let p = vec!["Na2O2", "MgO2", "SrO2"];
-        let n_p = vec!["CaO", "RaO", "Li2O"];
+        let p = vec!["Na2O2", "MgO2", "SrO2", "H2O2"];
+        let n_p = vec!["CaO", "RaO", "Li2O", "H2O"];
         let n = vec!["Ca3O2", "CoO2", "MoO5"];
 
         is_substance_class(p, n_p, n, SubstanceClass::Peroxide, vec![("O", -1)]);
@@ -658,7 +935,7 @@ mod tests {
 
     #[test]
     fn substance_acid() {
-        let a = vec!["B(OH)3", "HCl", "H2SiO3", "H3PO4"];
+        let a = vec!["B(OH)3", "HCl", "H2SiO3", "H3PO4", "HNO3", "CH3COOH"];
         let n_a = vec!["H4TiO4", "H2CaO2", "H2O"];
         let n = vec!["HPO4", "H3Cl", "H2ClO"];
 
@@ -674,6 +951,8 @@ mod tests {
             "Al(OH)2I",
             "LiKRbPO4",
             "CsAuCl4",
+            "Pb(NO3)2",
+            "Zn(CH3COO)2",
         ];
         let n_s = vec!["HCl", "Na(OH)"];
         let n = vec!["NaCl2", "CaPO4", "B5P3"];
@@ -681,6 +960,20 @@ mod tests {
         is_substance_class(s, n_s, n, SubstanceClass::Salt, vec![]);
     }
 
+    #[test]
+    fn substance_binary() {
+        let b = vec!["PCl3", "PCl5", "CS2", "CH4", "SF6", "SiCl4", "NCl3"];
+        let n_b = vec!["HCl", "SO2", "NH3", "H2S"];
+        let n = vec!["PCl4", "SF5"];
+
+        is_substance_class(b, n_b, n, SubstanceClass::Binary, vec![]);
+
+        let p_t = PeriodicTable::new();
+        let pcl5 = Substance::from_string("PCl5", &p_t).unwrap();
+        assert_eq!(5, pcl5.anti_me["P"].oxidation_state);
+        assert_eq!(-1, pcl5.anti_me["Cl"].oxidation_state);
+    }
+
     fn is_substance_class(
         g_class: Vec<&str>, // g stands for "group"
         g_not_class: Vec<&str>,
@@ -0,0 +1,73 @@
+use super::substance::{is_composition, Substance};
+use crate::{
+    quantity::{Energy, Entropy, Temperature},
+    utils::{periodic_table::PeriodicTable, thermo_table::THERMODYNAMICS},
+};
+
+// ΔH°, S° (ΔS° of reactions) and ΔG° at 25°C, per mol of a substance or of a reaction as written
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Thermodynamics {
+    pub enthalpy: Energy,
+    pub entropy: Entropy,
+    pub gibbs: Energy,
+}
+
+impl Thermodynamics {
+    // ΔG = ΔH - TΔS
+    pub fn gibbs_at(&self, temperature: Temperature) -> Energy {
+        self.enthalpy - self.entropy * temperature
+    }
+
+    pub fn is_exothermic(&self) -> bool {
+        self.enthalpy.kilojoules() < 0.0
+    }
+
+    pub fn is_spontaneous(&self, temperature: Temperature) -> bool {
+        self.gibbs_at(temperature).kilojoules() < 0.0
+    }
+
+    // ΔG changes its sign at T = ΔH / ΔS if both have the same sign
+    pub fn crossover_temperature(&self) -> Option<Temperature> {
+        let (h, s) = (self.enthalpy.kilojoules(), self.entropy.joules_per_kelvin());
+        (h * s > 0.0).then(|| self.enthalpy / self.entropy)
+    }
+}
+
+impl Substance {
+    pub fn thermodynamics(&self, p_t: &PeriodicTable) -> Option<Thermodynamics> {
+        let sbs = self.blocks();
+        THERMODYNAMICS
+            .iter()
+            .find(|(formula, _, _, _)| is_composition(&sbs, formula, p_t))
+            .map(|(_, h, s, g)| Thermodynamics {
+                enthalpy: Energy::from_kilojoules(*h),
+                entropy: Entropy::from_joules_per_kelvin(*s),
+                gibbs: Energy::from_kilojoules(*g),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substance_thermodynamics() {
+        let p_t = PeriodicTable::new();
+        let water = Substance::from_string("H2O", &p_t).unwrap();
+        let data = water.thermodynamics(&p_t).unwrap();
+        assert_eq!(-285.8, data.enthalpy.kilojoules());
+        assert_eq!(70.0, data.entropy.joules_per_kelvin());
+
+        // ΔG°f from the table is close to ΔH°f - TS° of the formation from H₂ and ½O₂
+        let room = Temperature::from_celsius(25.0);
+        let formation = Thermodynamics {
+            entropy: Entropy::from_joules_per_kelvin(70.0 - 130.7 - 205.2 / 2.0),
+            ..data
+        };
+        assert!((data.gibbs - formation.gibbs_at(room)).kilojoules().abs() < 0.1);
+
+        let kmno4 = Substance::from_string("KMnO4", &p_t).unwrap();
+        assert_eq!(None, kmno4.thermodynamics(&p_t));
+    }
+}
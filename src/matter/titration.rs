@@ -0,0 +1,268 @@
+use super::{
+    ph::{AcidBaseMixture, Protolyte},
+    substance::{Substance, SubstanceClass},
+};
+use crate::{
+    quantity::{Molarity, Volume},
+    utils::{indicator_table::INDICATORS, periodic_table::PeriodicTable},
+};
+
+// Part of the titrant of a step added around the point to see the pH jump
+const JUMP_WINDOW: f64 = 0.01;
+// Smaller jumps can't be seen on the curve
+const MIN_JUMP: f64 = 0.5;
+// Indicator has to change its colour within a drop: 0.1% of the titrant of a step
+const INDICATOR_WINDOW: f64 = 0.001;
+// The curve goes on till this many volumes of the last equivalence point
+const CURVE_END: f64 = 1.5;
+
+// Titrant from the burette is added to the analyte in the flask
+#[derive(Debug, Clone)]
+pub struct Titration {
+    pub analyte: Substance,
+    pub analyte_concentration: Molarity,
+    pub analyte_volume: Volume,
+    pub titrant: Substance,
+    pub titrant_concentration: Molarity,
+    // of both solutions before they are mixed, pH of every point is found from them
+    analyte_protolytes: Vec<Protolyte>,
+    titrant_protolytes: Vec<Protolyte>,
+    // H⁺ or OH⁻ which 1 mol of each gives
+    analyte_factor: Option<u8>,
+    titrant_factor: Option<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquivalencePoint {
+    // of the titrant
+    pub volume: Volume,
+    pub ph: f64,
+    // halfway from the previous point, it's about pKa of a weak acid
+    pub half_equivalence_ph: f64,
+    // pH before and after the point
+    pub jump: (f64, f64),
+    pub indicators: Vec<&'static str>,
+}
+
+impl Titration {
+    pub fn new(
+        analyte: Substance,
+        analyte_concentration: Molarity,
+        analyte_volume: Volume,
+        titrant: Substance,
+        titrant_concentration: Molarity,
+        p_t: &PeriodicTable,
+    ) -> Result<Self, &'static str> {
+        match (analyte.class, titrant.class) {
+            (SubstanceClass::Acid, SubstanceClass::Base)
+            | (SubstanceClass::Base, SubstanceClass::Acid) => (),
+            _ => return Err("Acid is titrated with a base and a base - with an acid"),
+        }
+        if analyte_concentration.molarity() <= 0.0
+            || titrant_concentration.molarity() <= 0.0
+            || analyte_volume.litres() <= 0.0
+        {
+            return Err("Concentrations and volume have to be positive");
+        }
+        // pH has to be known for both
+        let analyte_protolytes = analyte.protolytes(analyte_concentration, p_t)?;
+        let titrant_protolytes = titrant.protolytes(titrant_concentration, p_t)?;
+        Ok(Self {
+            analyte_concentration,
+            analyte_volume,
+            titrant_concentration,
+            analyte_protolytes,
+            titrant_protolytes,
+            analyte_factor: analyte.equivalence_factor(p_t),
+            titrant_factor: titrant.equivalence_factor(p_t),
+            analyte,
+            titrant,
+        })
+    }
+
+    // Titrant which neutralises the first n H⁺ or OH⁻ of every analyte particle
+    pub fn stoichiometric_volume(&self, n: f64) -> Result<Volume, &'static str> {
+        let z = self
+            .titrant_factor
+            .ok_or("Titrant doesn't give H⁺ or OH⁻")?;
+        let amount = self.analyte_volume * self.analyte_concentration * n;
+        Ok(amount / (self.titrant_concentration * z as f64))
+    }
+
+    pub fn ph_at(&self, volume: Volume) -> Result<f64, &'static str> {
+        if volume.litres() < 0.0 {
+            return Err("Volume of the titrant has to be positive");
+        }
+        let total = self.analyte_volume + volume;
+        let mut mixture = AcidBaseMixture::new();
+        let portions = [
+            (&self.analyte_protolytes, self.analyte_volume),
+            (&self.titrant_protolytes, volume),
+        ];
+        for (protolytes, v) in portions {
+            for p in protolytes {
+                mixture.protolytes.push(p.diluted(v / total));
+            }
+        }
+        Ok(mixture.ph())
+    }
+
+    // Every H⁺ or OH⁻ of the analyte gives a point, the ones without a visible jump are skipped
+    pub fn equivalence_points(&self) -> Result<Vec<EquivalencePoint>, &'static str> {
+        let steps = self
+            .analyte_factor
+            .ok_or("Analyte doesn't give H⁺ or OH⁻")?;
+        let step = self.stoichiometric_volume(1.0)?;
+        let mut res = Vec::new();
+        for n in 1..=steps {
+            let volume = self.stoichiometric_volume(n as f64)?;
+            let around = |part: f64| -> Result<(f64, f64), &'static str> {
+                Ok((
+                    self.ph_at(volume - step * part)?,
+                    self.ph_at(volume + step * part)?,
+                ))
+            };
+            let jump = around(JUMP_WINDOW)?;
+            if (jump.1 - jump.0).abs() < MIN_JUMP {
+                continue;
+            }
+
+            let (a, b) = around(INDICATOR_WINDOW)?;
+            let (from, to) = (a.min(b), a.max(b));
+            let indicators = INDICATORS
+                .iter()
+                .filter(|(_, (low, high), _, _)| *low <= to && *high >= from)
+                .map(|i| i.0)
+                .collect();
+            res.push(EquivalencePoint {
+                volume,
+                ph: self.ph_at(volume)?,
+                half_equivalence_ph: self.ph_at(volume - step * 0.5)?,
+                jump,
+                indicators,
+            });
+        }
+        Ok(res)
+    }
+
+    // (volume of the titrant, pH) from 0 with the given step
+    pub fn curve(&self, step: Volume) -> Result<Vec<(Volume, f64)>, &'static str> {
+        if step.litres() <= 0.0 {
+            return Err("Step of the curve has to be positive");
+        }
+        let steps = self
+            .analyte_factor
+            .ok_or("Analyte doesn't give H⁺ or OH⁻")?;
+        let end = self.stoichiometric_volume(steps as f64)? * CURVE_END;
+        let n = (end / step).ceil() as usize;
+        (0..=n)
+            .map(|i| {
+                let volume = step * i as f64;
+                Ok((volume, self.ph_at(volume)?))
+            })
+            .collect()
+    }
+
+    // V (mL),pH
+    // 0.00,2.88
+    pub fn to_csv(&self, step: Volume) -> Result<String, &'static str> {
+        let mut res = String::from("V (mL),pH\n");
+        for (volume, ph) in self.curve(step)? {
+            res.push_str(&format!("{:.2},{:.2}\n", volume.to("mL")?, ph));
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::periodic_table::PeriodicTable;
+
+    fn titration(analyte: &str, c: f64, titrant: &str, c_t: f64) -> Titration {
+        let p_t = PeriodicTable::new();
+        Titration::new(
+            Substance::from_string(analyte, &p_t).unwrap(),
+            Molarity::from_molarity(c),
+            "20 mL".parse().unwrap(),
+            Substance::from_string(titrant, &p_t).unwrap(),
+            Molarity::from_molarity(c_t),
+            &p_t,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn titration_curve() {
+        let close = |a: f64, b: f64| (a - b).abs() < 0.05;
+        let ml = |v: Volume| v.to("mL").unwrap();
+
+        // strong acid with strong base
+        let hcl = titration("HCl", 0.1, "NaOH", 0.1);
+        assert!(close(1.0, hcl.ph_at(Volume::default()).unwrap()));
+        let points = hcl.equivalence_points().unwrap();
+        assert_eq!(1, points.len());
+        assert!(close(20.0, ml(points[0].volume)));
+        assert!(close(7.0, points[0].ph));
+        assert!(points[0].indicators.contains(&"methyl orange"));
+        assert!(points[0].indicators.contains(&"phenolphthalein"));
+
+        // weak acid: pH = pKa halfway, the point is in base
+        let acetic = titration("CH3COOH", 0.1, "NaOH", 0.1);
+        let points = acetic.equivalence_points().unwrap();
+        assert!(close(8.72, points[0].ph));
+        assert!(close(4.76, points[0].half_equivalence_ph));
+        assert_eq!(
+            vec!["phenolphthalein", "thymolphthalein"],
+            points[0].indicators
+        );
+
+        // weak base with strong acid, the point is in acid
+        let ammonia = titration("NH4OH", 0.1, "HCl", 0.1);
+        let points = ammonia.equivalence_points().unwrap();
+        assert!(close(5.28, points[0].ph));
+        assert!(points[0].indicators.contains(&"methyl red"));
+        assert!(!points[0].indicators.contains(&"phenolphthalein"));
+
+        // the third H⁺ of H₃PO₄ gives no jump
+        let phosphoric = titration("H3PO4", 0.1, "NaOH", 0.1);
+        let points = phosphoric.equivalence_points().unwrap();
+        assert_eq!(2, points.len());
+        assert!(close(20.0, ml(points[0].volume)) && close(40.0, ml(points[1].volume)));
+        assert!(close(7.2, points[1].half_equivalence_ph));
+        assert!(points[1].indicators.contains(&"phenolphthalein"));
+
+        // Ca(OH)₂ takes 2 HCl
+        let lime = titration("HCl", 0.1, "Ca(OH)2", 0.05);
+        assert!(close(
+            20.0,
+            ml(lime.equivalence_points().unwrap()[0].volume)
+        ));
+
+        let p_t = PeriodicTable::new();
+        let substance = |formula: &str| Substance::from_string(formula, &p_t).unwrap();
+        let c = Molarity::from_molarity(0.1);
+        let v = Volume::from_litres(0.02);
+        assert!(Titration::new(substance("HCl"), c, v, substance("HNO3"), c, &p_t).is_err());
+        assert!(Titration::new(substance("HCl"), c, v, substance("NaCl"), c, &p_t).is_err());
+    }
+
+    #[test]
+    fn titration_csv() {
+        let hcl = titration("HCl", 0.1, "NaOH", 0.1);
+        let csv = hcl.to_csv("1 mL".parse().unwrap()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        // 0..30 mL
+        assert_eq!(32, lines.len());
+        assert_eq!("V (mL),pH", lines[0]);
+        assert_eq!("0.00,1.00", lines[1]);
+        assert_eq!("20.00,7.00", lines[21]);
+        // pH only grows when a base is added
+        let ph: Vec<f64> = lines[1..]
+            .iter()
+            .map(|l| l.split(',').nth(1).unwrap().parse().unwrap())
+            .collect();
+        assert!(ph.windows(2).all(|w| w[0] <= w[1]));
+        assert!(hcl.curve(Volume::default()).is_err());
+    }
+}
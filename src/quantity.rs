@@ -0,0 +1,318 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+use std::str::FromStr;
+
+use crate::utils::unit_table::{
+    AMOUNT_UNITS, DENSITY_UNITS, ENERGY_UNITS, ENTROPY_UNITS, MASS_UNITS, MOLALITY_UNITS,
+    MOLARITY_UNITS, MOLAR_MASS_UNITS, PRESSURE_UNITS, TEMPERATURE_UNITS, VOLUME_UNITS,
+};
+
+// Value in the base unit of its table, the other units are converted on the way in and out;
+// quantities of one kind can be added, subtracted and divided into a plain ratio
+macro_rules! linear_quantity {
+    ($name:ident, $units:ident, $from_base:ident, $base:ident) => {
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+        pub struct $name(f64);
+
+        impl $name {
+            pub const fn $from_base(value: f64) -> Self {
+                Self(value)
+            }
+
+            pub fn $base(&self) -> f64 {
+                self.0
+            }
+
+            pub fn new(value: f64, unit: &str) -> Result<Self, &'static str> {
+                Ok(Self(value * factor(&$units, unit)?))
+            }
+
+            pub fn to(&self, unit: &str) -> Result<f64, &'static str> {
+                Ok(self.0 / factor(&$units, unit)?)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = &'static str;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let (value, unit) = split_value(s)?;
+                Self::new(value, unit)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} {}", self.0, $units[0].0)
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+            fn add(self, other: Self) -> Self {
+                Self(self.0 + other.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+            fn sub(self, other: Self) -> Self {
+                Self(self.0 - other.0)
+            }
+        }
+
+        impl Mul<f64> for $name {
+            type Output = Self;
+            fn mul(self, k: f64) -> Self {
+                Self(self.0 * k)
+            }
+        }
+
+        impl Mul<$name> for f64 {
+            type Output = $name;
+            fn mul(self, q: $name) -> $name {
+                $name(self * q.0)
+            }
+        }
+
+        impl Div<f64> for $name {
+            type Output = Self;
+            fn div(self, k: f64) -> Self {
+                Self(self.0 / k)
+            }
+        }
+
+        impl Div for $name {
+            type Output = f64;
+            fn div(self, other: Self) -> f64 {
+                self.0 / other.0
+            }
+        }
+    };
+}
+
+// a × b = c, b × a = c, c / a = b, c / b = a
+macro_rules! product_of {
+    ($a:ident, $b:ident, $c:ident) => {
+        impl Mul<$b> for $a {
+            type Output = $c;
+            fn mul(self, b: $b) -> $c {
+                $c(self.0 * b.0)
+            }
+        }
+
+        impl Mul<$a> for $b {
+            type Output = $c;
+            fn mul(self, a: $a) -> $c {
+                $c(self.0 * a.0)
+            }
+        }
+
+        impl Div<$a> for $c {
+            type Output = $b;
+            fn div(self, a: $a) -> $b {
+                $b(self.0 / a.0)
+            }
+        }
+
+        impl Div<$b> for $c {
+            type Output = $a;
+            fn div(self, b: $b) -> $a {
+                $a(self.0 / b.0)
+            }
+        }
+    };
+}
+
+linear_quantity!(Mass, MASS_UNITS, from_grams, grams);
+linear_quantity!(Amount, AMOUNT_UNITS, from_moles, moles);
+linear_quantity!(Volume, VOLUME_UNITS, from_litres, litres);
+linear_quantity!(Pressure, PRESSURE_UNITS, from_kilopascals, kilopascals);
+linear_quantity!(Energy, ENERGY_UNITS, from_kilojoules, kilojoules);
+linear_quantity!(
+    Entropy,
+    ENTROPY_UNITS,
+    from_joules_per_kelvin,
+    joules_per_kelvin
+);
+linear_quantity!(Molarity, MOLARITY_UNITS, from_molarity, molarity);
+linear_quantity!(
+    MolarMass,
+    MOLAR_MASS_UNITS,
+    from_grams_per_mole,
+    grams_per_mole
+);
+linear_quantity!(
+    Density,
+    DENSITY_UNITS,
+    from_grams_per_litre,
+    grams_per_litre
+);
+linear_quantity!(
+    Molality,
+    MOLALITY_UNITS,
+    from_moles_per_kilogram,
+    moles_per_kilogram
+);
+
+// g = mol × g/mol, mol = L × mol/L, g = L × g/L
+product_of!(Amount, MolarMass, Mass);
+product_of!(Volume, Molarity, Amount);
+product_of!(Volume, Density, Mass);
+
+// 1 atm
+pub const NORMAL_PRESSURE: Pressure = Pressure::from_kilopascals(101.325);
+
+// PV = nRT: kJ / kPa = m³
+impl Div<Pressure> for Energy {
+    type Output = Volume;
+    fn div(self, p: Pressure) -> Volume {
+        Volume(self.0 / p.0 * 1e3)
+    }
+}
+
+// Absolute temperature: it isn't added up, units differ by their zero too
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Temperature(f64);
+
+impl Temperature {
+    pub const fn from_kelvin(value: f64) -> Self {
+        Self(value)
+    }
+
+    pub const fn from_celsius(value: f64) -> Self {
+        Self(value + 273.15)
+    }
+
+    pub fn kelvin(&self) -> f64 {
+        self.0
+    }
+
+    pub fn celsius(&self) -> f64 {
+        self.0 - 273.15
+    }
+
+    pub fn new(value: f64, unit: &str) -> Result<Self, &'static str> {
+        let (_, factor, offset) = temperature_unit(unit)?;
+        let kelvin = value * factor + offset;
+        if kelvin < 0.0 {
+            return Err("Temperature can't be below absolute zero");
+        }
+        Ok(Self(kelvin))
+    }
+
+    pub fn to(&self, unit: &str) -> Result<f64, &'static str> {
+        let (_, factor, offset) = temperature_unit(unit)?;
+        Ok((self.0 - offset) / factor)
+    }
+}
+
+// TΔS: J/K × K = J
+impl Mul<Temperature> for Entropy {
+    type Output = Energy;
+    fn mul(self, t: Temperature) -> Energy {
+        Energy(self.0 * t.0 / 1e3)
+    }
+}
+
+// T = ΔH / ΔS
+impl Div<Entropy> for Energy {
+    type Output = Temperature;
+    fn div(self, s: Entropy) -> Temperature {
+        Temperature(self.0 * 1e3 / s.0)
+    }
+}
+
+impl FromStr for Temperature {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (value, unit) = split_value(s)?;
+        Self::new(value, unit)
+    }
+}
+
+impl fmt::Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} K", self.0)
+    }
+}
+
+fn factor(units: &[(&str, f64)], unit: &str) -> Result<f64, &'static str> {
+    units
+        .iter()
+        .find(|(u, _)| *u == unit)
+        .map(|(_, k)| *k)
+        .ok_or("Unknown unit")
+}
+
+fn temperature_unit(unit: &str) -> Result<&'static (&'static str, f64, f64), &'static str> {
+    TEMPERATURE_UNITS
+        .iter()
+        .find(|(u, _, _)| *u == unit)
+        .ok_or("Unknown unit")
+}
+
+// "2.5 g", "300mL" or "1e-3 g" - the longest number and the unit after it
+fn split_value(s: &str) -> Result<(f64, &str), &'static str> {
+    let s = s.trim();
+    let numeric = s
+        .find(|c: char| !(c.is_ascii_digit() || ['.', '-', '+', 'e', 'E'].contains(&c)))
+        .unwrap_or(s.len());
+    let (end, value) = (1..=numeric)
+        .rev()
+        .find_map(|end| s[..end].parse::<f64>().ok().map(|v| (end, v)))
+        .ok_or("Wrong number")?;
+    Ok((value, s[end..].trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantity_units() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+
+        let mass: Mass = "2.5 kg".parse().unwrap();
+        assert!(close(2500.0, mass.grams()));
+        assert!(close(2.5e6, mass.to("mg").unwrap()));
+        let volume: Volume = "300mL".parse().unwrap();
+        assert!(close(0.3, volume.litres()));
+        let pressure: Pressure = "760 mmHg".parse().unwrap();
+        assert!((1.0 - pressure.to("atm").unwrap()).abs() < 1e-4);
+        let temperature: Temperature = "25 °C".parse().unwrap();
+        assert!(close(298.15, temperature.kelvin()));
+        assert!(close(77.0, temperature.to("°F").unwrap()));
+
+        assert!("2.5 parsecs".parse::<Mass>().is_err());
+        assert!("g".parse::<Mass>().is_err());
+        let mass: Mass = "1e-3 g".parse().unwrap();
+        assert!(close(1.0, mass.to("mg").unwrap()));
+        let volume: Volume = "2.5E2mL".parse().unwrap();
+        assert!(close(0.25, volume.litres()));
+        assert!("-300 °C".parse::<Temperature>().is_err());
+        assert_eq!("1500 g", (Mass::from_grams(1000.0) * 1.5).to_string());
+    }
+
+    #[test]
+    fn quantity_arithmetic() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+
+        let water = MolarMass::from_grams_per_mole(18.0);
+        let amount: Amount = Mass::from_grams(36.0) / water;
+        assert!(close(2.0, amount.moles()));
+        assert!(close(36.0, (amount * water).grams()));
+
+        let solution = Volume::new(250.0, "mL").unwrap() * Molarity::new(2.0, "M").unwrap();
+        assert!(close(0.5, solution.moles()));
+        assert!(close(
+            2.0,
+            (solution / Volume::from_litres(0.25)).molarity()
+        ));
+        assert!(close(
+            0.5,
+            Amount::from_moles(1.0) / Amount::from_moles(2.0)
+        ));
+    }
+}
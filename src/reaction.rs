@@ -1,13 +1,39 @@
 use std::collections::HashMap;
+use std::fmt;
 
 use crate::{
     matter::{
+        acid::Strength,
         element::Element,
-        substance::{Substance, SubstanceBlock as SB, SubstanceClass as SC},
+        ion::{subtract, Ion},
+        oxide::OxideKind,
+        solubility::Solubility,
+        substance::{
+            composition_ratio, is_composition, Substance, SubstanceBlock as SB,
+            SubstanceClass as SC,
+        },
+    },
+    utils::{
+        decomposition_table::DECOMPOSITIONS,
+        math_util::{lcm, null_vector},
+        periodic_table::PeriodicTable,
+        potential_table::{ION_POTENTIALS, STANDARD_POTENTIALS},
+        redox_table::{OXIDISERS, REDUCERS},
     },
-    utils::{math_util::lcm, periodic_table::PeriodicTable},
 };
 
+pub mod conditions;
+pub mod equilibrium;
+pub mod half_reaction;
+pub mod ionic;
+pub mod redox;
+pub mod rules;
+pub mod stoichiometry;
+pub mod thermochemistry;
+
+use conditions::Conditions;
+use rules::RuleSet;
+
 // Electrochmical series of metalls. Where:
 // from left to right the standard electrochemical potential increases
 const ACTIVE_METALLS: [&str; 8] = ["Li", "Cs", "Rb", "K", "Ba", "Sr", "Ca", "Na"];
@@ -15,8 +41,52 @@ const MEDIUM_ACTIVE_METALLS: [&str; 12] = [
     "Mg", "Al", "Ti", "Mn", "Zn", "Cr", "Fe", "Cd", "Co", "Ni", "Sn", "Pb",
 ];
 const NON_ACTIVE_METALLS: [&str; 8] = ["Sb", "Bi", "Cu", "Hg", "Ag", "Pd", "Pt", "Au"];
+// Cold concentrated HNO₃ and H₂SO₄ cover them with an oxide film
+const PASSIVATED_METALLS: [&str; 3] = ["Fe", "Al", "Cr"];
+
+// Acids and bases which decompose as soon as they form
+const UNSTABLE: [(&str, [&str; 2]); 5] = [
+    ("H2CO3", ["CO2", "H2O"]),
+    ("H2SO3", ["SO2", "H2O"]),
+    ("NH4OH", ["NH3", "H2O"]),
+    ("AgOH", ["Ag2O", "H2O"]),
+    ("Hg(OH)2", ["HgO", "H2O"]),
+];
+
+// Sulfides which don't dissolve even in strong acids: CuSO₄ + H₂S = CuS + H₂SO₄
+const ACID_INSOLUBLE: [&str; 3] = ["CuS", "PbS", "Ag2S"];
+
+// The highest states non-metals reach in direct combination: S + O₂ = SO₂, N₂ + O₂ = 2NO
+const COMBINATION_LIMITS: [(&str, &str, i8); 4] =
+    [("S", "O", 4), ("N", "O", 2), ("Cl", "F", 3), ("Br", "F", 5)];
 
-#[derive(Debug, PartialEq)]
+// Acidic oxides which give two acids with water: 2NO₂ + H₂O = HNO₃ + HNO₂
+const OXIDE_WATER: [(&str, [&str; 2]); 2] =
+    [("NO2", ["HNO3", "HNO2"]), ("ClO2", ["HClO3", "HClO2"])];
+
+// Oxides which oxidise acids instead of giving salts: MnO₂ + 4HCl = MnCl₂ + Cl₂ + 2H₂O,
+// (oxide, charge of the cation it goes to)
+const OXIDISING_OXIDES: [(&str, i8); 2] = [("MnO2", 2), ("PbO2", 2)];
+
+// Metals which react with alkali solutions: Zn + 2NaOH + 2H₂O = Na₂[Zn(OH)₄] + H₂
+const AMPHOTERIC_METALLS: [&str; 4] = ["Be", "Zn", "Al", "Sn"];
+
+// Every rule gets reagents, their amounts in mol and conditions
+type ReactionFn = fn(
+    &Vec<Substance>,
+    Option<&[u8]>,
+    &Conditions,
+    &PeriodicTable,
+) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str>;
+
+// Ion which changes its state, the other ion of its substance and what it turns into
+struct RedoxAgent {
+    ion: Ion,
+    partner: Ion,
+    product: Ion,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum ReactionType {
     Combination,
     Decomposition,
@@ -28,9 +98,55 @@ pub enum ReactionType {
 #[derive(Debug)]
 pub struct Reaction {
     pub reagents: Vec<Substance>,
-    pub heating: bool,
+    pub conditions: Conditions,
     pub products: Vec<Substance>,
     pub rtype: ReactionType,
+    // why ReactionType::None
+    pub reason: Option<&'static str>,
+    // ⇌, it goes both ways to an equilibrium
+    pub reversible: bool,
+}
+
+impl fmt::Display for Reaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let p_t = PeriodicTable::new();
+        // without coefficients when the equation can't be balanced
+        let coefs = self.coefficients().ok();
+        let balanced = coefs.is_some();
+        let (r_coefs, p_coefs) =
+            coefs.unwrap_or((vec![1; self.reagents.len()], vec![1; self.products.len()]));
+        let side = |substances: &[Substance], coefs: &[u8], marked: bool| {
+            let mut res = Vec::new();
+            for (s, k) in substances.iter().zip(coefs) {
+                let formula = s.formula(&p_t);
+                let marker = match marked {
+                    true => self.marker(s, &p_t),
+                    false => "",
+                };
+                match k {
+                    1 => res.push(format!("{}{}", formula, marker)),
+                    k => res.push(format!("{}{}{}", k, formula, marker)),
+                }
+            }
+            res.join(" + ")
+        };
+
+        let reagents = side(&self.reagents, &r_coefs, false);
+        let arrow = match self.reversible {
+            true => "⇌",
+            false => "→",
+        };
+        match self.products.is_empty() {
+            true => write!(f, "{} → no reaction", reagents),
+            false => {
+                let products = side(&self.products, &p_coefs, true);
+                match balanced {
+                    true => write!(f, "{} {} {}", reagents, arrow, products),
+                    false => write!(f, "{} {} {} (not balanced)", reagents, arrow, products),
+                }
+            }
+        }
+    }
 }
 
 impl Reaction {
@@ -38,97 +154,132 @@ impl Reaction {
         reagents: Vec<Substance>,
         heating: bool,
     ) -> Result<Self, &'static str> {
-        if reagents.len() != 2 {
-            panic!("Supports reaction simulation only for 2 reagents")
+        Self::calculate(
+            reagents,
+            None,
+            Conditions {
+                heating,
+                ..Default::default()
+            },
+            RuleSet::defaults(),
+        )
+    }
+
+    // Amounts of reagents in mol define the products: NaOH + H₂SO₄ -> NaHSO₄ + H₂O
+    pub fn try_calculate_in_ratio(
+        reagents: Vec<Substance>,
+        amounts: Vec<u8>,
+        heating: bool,
+    ) -> Result<Self, &'static str> {
+        if amounts.len() != reagents.len() || amounts.contains(&0) {
+            return Err("Every reagent needs its amount");
+        }
+        Self::calculate(
+            reagents,
+            Some(&amounts),
+            Conditions {
+                heating,
+                ..Default::default()
+            },
+            RuleSet::defaults(),
+        )
+    }
+
+    // Temperature, catalyst, excess of a reagent and so on
+    pub fn try_calculate_with(
+        reagents: Vec<Substance>,
+        conditions: Conditions,
+    ) -> Result<Self, &'static str> {
+        Self::calculate(reagents, None, conditions, RuleSet::defaults())
+    }
+
+    // Rules of the set are used instead of the default ones
+    pub fn try_calculate_with_rules(
+        reagents: Vec<Substance>,
+        conditions: Conditions,
+        rules: &RuleSet,
+    ) -> Result<Self, &'static str> {
+        Self::calculate(reagents, None, conditions, rules)
+    }
+
+    // None amounts - as much of every reagent as the reaction needs,
+    // unless one of them is in excess
+    fn calculate(
+        reagents: Vec<Substance>,
+        amounts: Option<&[u8]>,
+        conditions: Conditions,
+        rules: &RuleSet,
+    ) -> Result<Self, &'static str> {
+        if reagents.is_empty() {
+            return Err("There are no reagents");
         }
 
         let p_t = PeriodicTable::new();
-        let reaction_func = match Self::determine_class(&reagents, &p_t) {
-            Ok(f) => f,
-            Err(e) => return Err(e),
-        };
+        let rule = rules
+            .find(&reagents, &conditions, &p_t)
+            .ok_or("Unknown class of reaction")?;
 
-        let (products, rtype) = match reaction_func(&reagents, heating, &p_t) {
+        let excess_amounts = conditions.excess_amounts(reagents.len());
+        let amounts = amounts.or(excess_amounts.as_deref());
+        let (products, rtype, reason) = match rule.apply(&reagents, amounts, &conditions, &p_t) {
             Ok(p) => p,
             Err(e) => return Err(e),
         };
 
         Ok(Self {
             reagents,
-            heating,
+            conditions,
             products,
             rtype,
+            reason,
+            reversible: false,
         })
     }
 
-    fn determine_class(
-        reagents: &Vec<Substance>,
-        p_t: &PeriodicTable,
-    ) -> Result<
-        fn(
-            &Vec<Substance>,
-            bool,
-            &PeriodicTable,
-        ) -> Result<(Vec<Substance>, ReactionType), &'static str>,
-        &'static str,
-    > {
-        // Classes of reagents
-        let mut reagent_classes = HashMap::from([
-            (SC::Simple, 0),
-            (SC::Hydride, 0),
-            (SC::Oxide, 0),
-            (SC::Peroxide, 0),
-            (SC::Base, 0),
-            (SC::Acid, 0),
-            (SC::Salt, 0),
-        ]);
-
-        // Some bools
-        let mut contains_simple_me = false;
-        let mut contains_simple_ame = false;
-        let mut contains_water = false;
-
-        // Some substances for comparing
-        let water = Substance::from_string("H2O", &p_t).unwrap();
-
-        // Fill characteristics of reagents
-        for substance in reagents {
-            *reagent_classes.get_mut(&substance.class).unwrap() += 1;
-
-            match (substance.me.len(), substance.anti_me.len()) {
-                (1, 0) => contains_simple_me = true,
-                (0, 1) => contains_simple_ame = true,
-                (_, _) => (),
-            }
-
-            if substance.eq(&water) {
-                contains_water = true;
-            }
-        }
-
-        if reagents.len() == 2 && contains_simple_me && contains_simple_ame {
-            Ok(Self::reaction_me_antime)
-        } else if reagents.len() == 2 && contains_simple_me && contains_water {
-            Ok(Self::reaction_me_water)
-        } else if reagents.len() == 2 && contains_simple_me && reagent_classes[&SC::Acid] == 1 {
-            Ok(Self::reaction_me_acid)
-        } else {
-            Err(&"Unknown class of reaction")
+    // ↑ for gases and ↓ for precipitates which the reaction gives
+    pub fn marker(&self, product: &Substance, p_t: &PeriodicTable) -> &'static str {
+        if product.is_gas(p_t) && !self.reagents.iter().any(|r| r.is_gas(p_t)) {
+            return "↑";
         }
+        let in_solution = self.reagents.iter().any(|r| {
+            matches!(
+                r.solubility(p_t),
+                Some(Solubility::Soluble | Solubility::SlightlySoluble)
+            )
+        });
+        match product.solubility(p_t) {
+            Some(Solubility::Insoluble | Solubility::SlightlySoluble) if in_solution => "↓",
+            _ => "",
+        }
+    }
+
+    // Coefficients of reagents and products: 4Al + 3O₂ -> 2Al₂O₃
+    pub fn coefficients(&self) -> Result<(Vec<u8>, Vec<u8>), &'static str> {
+        balance(&self.reagents, &self.products)
     }
 
     fn reaction_me_antime(
         reagents: &Vec<Substance>,
-        heating: bool,
+        _amounts: Option<&[u8]>,
+        conditions: &Conditions,
         p_t: &PeriodicTable,
-    ) -> Result<(Vec<Substance>, ReactionType), &'static str> {
+    ) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str> {
+        let heating = conditions.is_heated();
         let rtype = ReactionType::Combination;
         let (me_name, me_element) = get_simple_me_from_reagents(reagents);
         let (ame_name, ame_element) = get_simple_antime_from_reagents(reagents);
 
+        if ame_element.group == 18 {
+            let reason = "Noble gases don't react with metals";
+            return Ok((vec![], ReactionType::None, Some(reason)));
+        }
+
         // Exceptions to the rules
         match (ame_element.charge, me_element.charge) {
-            (8, 47 | 78 | 79) => return Ok((vec![], ReactionType::None)), // Oxyd and Ag Pt Au
+            (8, 47 | 78 | 79) => {
+                let reason = "Ag, Pt and Au don't react with oxygen";
+                return Ok((vec![], ReactionType::None, Some(reason)));
+            }
             _ => (),
         }
 
@@ -138,14 +289,18 @@ impl Reaction {
             (3..=5, true) => me_element.group as i8, // statistics + logic
             (6, true) => me_element.valencies[(me_element.valencies.len() - 1) / 2] as i8, // statistics
             (_, true) => *me_element.valencies.last().unwrap() as i8,                      // xd
-            (_, false) => return Ok((vec![], ReactionType::None)),
+            (_, false) => {
+                let reason = "Heating is required";
+                return Ok((vec![], ReactionType::None, Some(reason)));
+            }
         };
 
         // Try to guess oxydation of anti metall
         let ame_oxydation = (18 - ame_element.group) as i8 * -1;
 
         // Calculate indexes
-        let (mut me_index, mut ame_index) = calculate_indexes_for_2(me_oxydation, ame_oxydation);
+        let (mut me_index, mut ame_index) = calculate_indexes_for_2(me_oxydation, ame_oxydation)
+            .ok_or("No oxidation state fits")?;
 
         // Exceptions to the rules
         match (ame_element.charge, me_element.charge) {
@@ -160,19 +315,105 @@ impl Reaction {
         let mut map = HashMap::new();
         map.insert(me_name, SB::new(me_element, me_index, 0));
         map.insert(ame_name, SB::new(ame_element, ame_index, 0));
-        let substance = match Substance::from_elements(map) {
+        let substance = match Substance::from_elements(map, p_t) {
             Ok(s) => s,
             Err(e) => return Err(e),
         };
 
-        Ok((vec![substance], rtype))
+        Ok((vec![substance], rtype, None))
+    }
+
+    // The more electronegative element is the negative one: S + O₂ = SO₂, H₂ + Cl₂ = 2HCl.
+    // The lack of it gives lower states: 2C + O₂ = 2CO, 2P + 3Cl₂ = 2PCl₃
+    fn reaction_nonme_nonme(
+        reagents: &Vec<Substance>,
+        amounts: Option<&[u8]>,
+        conditions: &Conditions,
+        p_t: &PeriodicTable,
+    ) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str> {
+        let heating = conditions.is_heated();
+        let rtype = ReactionType::Combination;
+        let mut elements = Vec::new();
+        for (i, substance) in (0..).zip(reagents) {
+            let (name, sb) = substance.blocks().into_iter().next().unwrap();
+            elements.push((i, name, sb));
+        }
+        elements.sort_by(|a, b| {
+            let a = a.2.element.electronegativity;
+            let b = b.2.element.electronegativity;
+            a.total_cmp(&b)
+        });
+        let [(pos_i, pos_name, pos), (neg_i, neg_name, neg)] = elements.as_slice() else {
+            return Err("Two simple substances are required");
+        };
+
+        // Exceptions to the rules
+        let reason = match (pos_name.as_str(), neg_name.as_str()) {
+            (p, n) if p == n => Some("Substances consist of the same element"),
+            _ if pos.element.group == 18 || neg.element.group == 18 => {
+                Some("Noble gases don't react")
+            }
+            (_, "O") if pos.element.group == 17 => Some("Halogens don't react with oxygen"),
+            ("N", _) if neg.element.group == 17 => Some("Nitrogen doesn't react with halogens"),
+            (_, "F") => None,
+            // H₂ + Cl₂ explodes in the light
+            ("H", "Cl") if conditions.light => None,
+            _ if !heating => Some("Heating is required"),
+            _ => None,
+        };
+        if reason.is_some() {
+            return Ok((vec![], ReactionType::None, reason));
+        }
+
+        let neg_oxydation = match neg_name.as_str() {
+            "H" => -1,
+            _ => neg.element.group as i8 - 18,
+        };
+        let limit = COMBINATION_LIMITS
+            .iter()
+            .find(|(p, n, _)| p == pos_name && n == neg_name)
+            .map_or(i8::MAX, |l| l.2);
+        let states: Vec<i8> = match pos_name.as_str() {
+            "H" => vec![1],
+            _ => pos.element.valencies.iter().map(|v| *v as i8).collect(),
+        };
+        let states: Vec<i8> = states.into_iter().filter(|s| *s <= limit).collect();
+        let (Some(lowest), Some(highest)) = (states.first(), states.last()) else {
+            return Err("No oxidation state fits");
+        };
+
+        // the highest state for the excess of the negative element,
+        // otherwise the highest one it's enough for
+        let pos_oxydation = match amounts {
+            None => *highest,
+            Some(amounts) => {
+                let atoms = amounts[*pos_i] as i16 * pos.index as i16;
+                let electrons = amounts[*neg_i] as i16 * neg.index as i16 * -neg_oxydation as i16;
+                *states
+                    .iter()
+                    .rev()
+                    .find(|s| **s as i16 * atoms <= electrons)
+                    .unwrap_or(lowest)
+            }
+        };
+
+        let (pos_index, neg_index) = calculate_indexes_for_2(pos_oxydation, neg_oxydation)
+            .ok_or("No oxidation state fits")?;
+        let mut map = HashMap::new();
+        map.insert(pos_name.clone(), SB::new(pos.element.clone(), pos_index, 0));
+        map.insert(neg_name.clone(), SB::new(neg.element.clone(), neg_index, 0));
+        let substance = Substance::from_elements(map, p_t)?;
+
+        Ok((vec![substance], rtype, None))
     }
 
     fn reaction_me_water(
         reagents: &Vec<Substance>,
-        heating: bool,
+        _amounts: Option<&[u8]>,
+        conditions: &Conditions,
         p_t: &PeriodicTable,
-    ) -> Result<(Vec<Substance>, ReactionType), &'static str> {
+    ) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str> {
+        let heating = conditions.is_heated();
         let rtype = ReactionType::Substition;
         let (me_name, me_element) = get_simple_me_from_reagents(reagents);
 
@@ -181,7 +422,8 @@ impl Reaction {
             let base_oxydation = -1 as i8; // Base always has this oxydation
             let me_oxydation = me_element.group as i8;
 
-            let (me_index, base_index) = calculate_indexes_for_2(me_oxydation, base_oxydation);
+            let (me_index, base_index) = calculate_indexes_for_2(me_oxydation, base_oxydation)
+                .ok_or("No oxidation state fits")?;
 
             let mut map = HashMap::new();
             map.insert(me_name, SB::new(me_element, me_index, 0));
@@ -194,70 +436,1160 @@ impl Reaction {
                 SB::new(p_t.get("H").unwrap().clone(), base_index, 0),
             );
 
-            let base_substance = match Substance::from_elements(map) {
+            let base_substance = match Substance::from_elements(map, p_t) {
                 Ok(s) => s,
                 Err(e) => return Err(e),
             };
             let hydrogen = Substance::from_string("H2", p_t).unwrap();
 
-            return Ok((vec![base_substance, hydrogen], rtype));
+            return Ok((vec![base_substance, hydrogen], rtype, None));
         }
         // medium_active_metall + water = oxyde + H2 (Heating is required)
         else if MEDIUM_ACTIVE_METALLS.contains(&me_name.as_str()) && heating {
             let metall_substance = get_substance_from_reagents(reagents, 1, 0).unwrap();
             let oxygen_substance = Substance::from_string("O2", p_t).unwrap();
-            let oxyde =
-                Self::reaction_me_antime(&vec![metall_substance, oxygen_substance], heating, p_t)
-                    .unwrap()
-                    .0
-                    .iter()
-                    .next()
-                    .unwrap()
-                    .clone();
+            let oxyde = Self::reaction_me_antime(
+                &vec![metall_substance, oxygen_substance],
+                None,
+                conditions,
+                p_t,
+            )
+            .unwrap()
+            .0
+            .iter()
+            .next()
+            .unwrap()
+            .clone();
 
             let hydrogen = Substance::from_string("H2", p_t).unwrap();
 
-            return Ok((vec![oxyde, hydrogen], rtype));
+            return Ok((vec![oxyde, hydrogen], rtype, None));
         } else {
             // non_active_metall + water or no heating = no reaction
-            return Ok((vec![], ReactionType::None));
+            let reason = match heating {
+                true => "Metal isn't active enough to react with water",
+                false => "Heating is required",
+            };
+            return Ok((vec![], ReactionType::None, Some(reason)));
         }
     }
 
+    // Oxide of active metal + water = alkali: Na₂O + H₂O = 2NaOH,
+    // acidic oxide + water = acid: SO₃ + H₂O = H₂SO₄, P₂O₅ + 3H₂O = 2H₃PO₄,
+    // saline hydride + water = alkali + H₂: NaH + H₂O = NaOH + H₂,
+    // peroxide + water = alkali + H₂O₂, or O₂ when heated: 2Na₂O₂ + 2H₂O = 4NaOH + O₂
+    fn reaction_water(
+        reagents: &Vec<Substance>,
+        _amounts: Option<&[u8]>,
+        conditions: &Conditions,
+        p_t: &PeriodicTable,
+    ) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str> {
+        let heating = conditions.is_heated();
+        let mut substance = None;
+        for reagent in reagents {
+            if !is_composition(&reagent.blocks(), "H2O", p_t) {
+                substance = Some(reagent);
+            }
+        }
+        let substance = substance.ok_or("There is nothing to react with water")?;
+        let oh = Ion::from_string("OH", -1, p_t)?;
+
+        let (products, rtype) = match substance.class {
+            SC::Oxide => {
+                let products = match substance.oxide_kind() {
+                    Some(OxideKind::Basic) => {
+                        let cation = substance.oxide_cation().ok_or("Unknown oxide")?;
+                        let me_name = cation.blocks.keys().next().ok_or("Unknown oxide")?;
+                        if !ACTIVE_METALLS.contains(&me_name.as_str()) {
+                            let reason = "Oxides of non-active metals don't react with water";
+                            return Ok((vec![], ReactionType::None, Some(reason)));
+                        }
+                        vec![get_salt(&cation, &oh, p_t)?]
+                    }
+                    Some(OxideKind::Acidic) => {
+                        let formula = substance.formula(p_t);
+                        if formula == "SiO2" {
+                            let reason = "SiO2 doesn't react with water";
+                            return Ok((vec![], ReactionType::None, Some(reason)));
+                        }
+                        match OXIDE_WATER.iter().find(|(oxide, _)| *oxide == formula) {
+                            Some((_, acids)) => acids
+                                .iter()
+                                .map(|acid| Substance::from_string(acid, p_t))
+                                .collect::<Result<Vec<_>, _>>()?,
+                            None => vec![substance.oxide_acid(p_t).ok_or("Oxide has no acid")?],
+                        }
+                    }
+                    _ => {
+                        let reason =
+                            "Amphoteric and non-salt-forming oxides don't react with water";
+                        return Ok((vec![], ReactionType::None, Some(reason)));
+                    }
+                };
+                (products, ReactionType::Combination)
+            }
+            SC::Hydride | SC::Peroxide => {
+                let (me_name, me) = match substance.me.iter().next() {
+                    Some(me) if me.1.element.group <= 2 && me.0 != "H" => me,
+                    _ => {
+                        let reason =
+                            "Only hydrides and peroxides of active metals react with water";
+                        return Ok((vec![], ReactionType::None, Some(reason)));
+                    }
+                };
+                let cation = Ion::new(
+                    HashMap::from([(
+                        me_name.clone(),
+                        SB::new(me.element.clone(), 1, me.oxidation_state),
+                    )]),
+                    me.oxidation_state,
+                );
+                let gas = match (&substance.class, heating) {
+                    (SC::Hydride, _) => "H2",
+                    (_, true) => "O2",
+                    (_, false) => "H2O2",
+                };
+                let products = vec![
+                    get_salt(&cation, &oh, p_t)?,
+                    Substance::from_string(gas, p_t)?,
+                ];
+                (products, ReactionType::Exchange)
+            }
+            _ => return Err("Unknown class of reaction"),
+        };
+
+        Ok((products, rtype, None))
+    }
+
     fn reaction_me_acid(
         reagents: &Vec<Substance>,
-        heating: bool,
+        _amounts: Option<&[u8]>,
+        _conditions: &Conditions,
         p_t: &PeriodicTable,
-    ) -> Result<(Vec<Substance>, ReactionType), &'static str> {
+    ) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str> {
         let rtype = ReactionType::Substition;
         let (me_name, me_element) = get_simple_me_from_reagents(reagents);
 
         if ACTIVE_METALLS.contains(&me_name.as_str())
             || MEDIUM_ACTIVE_METALLS.contains(&me_name.as_str())
         {
-            let (mut map, acid_r_oxydation) = get_acid_residue(reagents);
+            let (mut map, acid_r_oxydation) = get_acid_residue(reagents, p_t);
 
             let me_oxydation = *me_element.valencies.first().unwrap() as i8;
-            let (me_index, acid_r_index) = calculate_indexes_for_2(me_oxydation, acid_r_oxydation);
+            let (me_index, acid_r_index) =
+                calculate_indexes_for_2(me_oxydation, acid_r_oxydation).ok_or("Unknown acid")?;
             for (_, block) in map.iter_mut() {
                 block.index *= acid_r_index;
             }
 
             map.insert(me_name, SB::new(me_element, me_index, 0));
 
-            let substance_salt = Substance::from_elements(map).unwrap();
+            let substance_salt = Substance::from_elements(map, p_t)?;
             let substance_hydrogen = Substance::from_string("H2", p_t).unwrap();
 
             return Ok((
                 vec![substance_salt, substance_hydrogen],
                 ReactionType::Substition,
+                None,
             ));
         } else {
-            return Ok((vec![], ReactionType::None));
+            let reason = "Metal is less active than hydrogen";
+            return Ok((vec![], ReactionType::None, Some(reason)));
+        }
+    }
+
+    // HNO₃ and concentrated H₂SO₄ oxidise metals with N and S, not with H⁺:
+    // Cu + 4HNO₃(conc) = Cu(NO₃)₂ + 2NO₂ + 2H₂O. The more active metal
+    // and the more dilute acid - the deeper N and S are reduced
+    fn reaction_me_oxidising_acid(
+        reagents: &Vec<Substance>,
+        _amounts: Option<&[u8]>,
+        conditions: &Conditions,
+        p_t: &PeriodicTable,
+    ) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str> {
+        let heating = conditions.is_heated();
+        let (me_name, me_element) = get_simple_me_from_reagents(reagents);
+        let acid = get_class_from_reagents(reagents, SC::Acid).ok_or("There is no acid")?;
+        let me = me_name.as_str();
+        let nitric = acid.formula(p_t) == "HNO3";
+        let concentrated = acid.is_concentrated(p_t);
+
+        if me == "Pt" || me == "Au" {
+            let reason = "Pt and Au don't react with HNO3 and H2SO4";
+            return Ok((vec![], ReactionType::None, Some(reason)));
+        }
+        let passivated = concentrated && PASSIVATED_METALLS.contains(&me);
+        if passivated && !heating {
+            let reason = "Cold concentrated acid passivates Fe, Al and Cr";
+            return Ok((vec![], ReactionType::None, Some(reason)));
+        }
+
+        // heated passivated metals react as non-active ones
+        let active = ACTIVE_METALLS.contains(&me) || me == "Mg";
+        let medium = !active && !passivated && MEDIUM_ACTIVE_METALLS.contains(&me);
+        if !active && !medium && !passivated && !NON_ACTIVE_METALLS.contains(&me) {
+            let reason = "Only metals are oxidised by the acid";
+            return Ok((vec![], ReactionType::None, Some(reason)));
+        }
+
+        let reduced = match (nitric, concentrated) {
+            (true, true) if active => "N2O",
+            (true, true) => "NO2",
+            (true, false) if (active || medium) && acid.is_very_dilute() => "NH4NO3",
+            (true, false) if active => "N2",
+            (true, false) => "NO",
+            (false, _) if active => "H2S",
+            (false, _) if medium => "S",
+            (false, _) => "SO2",
+        };
+
+        // oxidising acids give the highest state: Fe³⁺, not Fe²⁺
+        let me_oxydation = STANDARD_POTENTIALS
+            .iter()
+            .filter(|(name, _, _)| *name == me)
+            .map(|(_, charge, _)| *charge)
+            .max()
+            .unwrap_or(*me_element.valencies.first().unwrap() as i8);
+        let cation = Ion::new(
+            HashMap::from([(me_name.clone(), SB::new(me_element, 1, me_oxydation))]),
+            me_oxydation,
+        );
+        let residue = acid.acid_properties(p_t).ok_or("Unknown acid")?.residue;
+
+        Ok((
+            vec![
+                get_salt(&cation, &residue, p_t)?,
+                Substance::from_string(reduced, p_t)?,
+                Substance::from_string("H2O", p_t)?,
+            ],
+            ReactionType::Substition,
+            None,
+        ))
+    }
+
+    // Metal + salt = salt + metal, when the metal is more active than the one of salt:
+    // Fe + CuSO₄ = FeSO₄ + Cu
+    fn reaction_me_salt(
+        reagents: &Vec<Substance>,
+        _amounts: Option<&[u8]>,
+        _conditions: &Conditions,
+        p_t: &PeriodicTable,
+    ) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str> {
+        let (me_name, me_element) = get_simple_me_from_reagents(reagents);
+        let salt = get_class_from_reagents(reagents, SC::Salt).ok_or("There is no salt")?;
+
+        if ACTIVE_METALLS.contains(&me_name.as_str()) {
+            let reason = "Active metal reacts with water of the solution, not with the salt";
+            return Ok((vec![], ReactionType::None, Some(reason)));
+        }
+        if !matches!(
+            salt.solubility(p_t),
+            Some(Solubility::Soluble | Solubility::SlightlySoluble)
+        ) {
+            let reason = "Metals displace each other only from salt solutions";
+            return Ok((vec![], ReactionType::None, Some(reason)));
+        }
+
+        let (salt_cation, salt_anion) = get_exchange_ions(salt, p_t)?;
+        let (salt_me_name, salt_me) = match salt_cation.blocks.iter().next() {
+            Some((name, sb)) if salt_cation.blocks.len() == 1 => (name.clone(), sb.clone()),
+            _ => {
+                let reason = "Only metal cations can be displaced";
+                return Ok((vec![], ReactionType::None, Some(reason)));
+            }
+        };
+
+        // Fe goes to Fe²⁺, not Fe³⁺
+        let (me_oxydation, me_potential) =
+            get_standard_potential(&me_name, None).ok_or("Unknown potential of the metal")?;
+        let me_cation = HashMap::from([(me_name.clone(), SB::new(me_element, 1, me_oxydation))]);
+        let substance_salt = get_salt(&Ion::new(me_cation, me_oxydation), &salt_anion, p_t)?;
+
+        // Fe³⁺ takes one electron: 2FeCl₃ + Cu = 2FeCl₂ + CuCl₂, 2FeCl₃ + Fe = 3FeCl₂
+        if let Some((_, _, lower, potential)) = ION_POTENTIALS
+            .iter()
+            .find(|(name, charge, _, _)| *name == salt_me_name && *charge == salt_cation.charge)
+        {
+            if me_potential >= *potential {
+                let reason = "Metal is less active than the one of the salt";
+                return Ok((vec![], ReactionType::None, Some(reason)));
+            }
+            let lower_cation = HashMap::from([(salt_me_name, SB::new(salt_me.element, 1, *lower))]);
+            let mut products = vec![substance_salt];
+            push_unique(
+                &mut products,
+                get_salt(&Ion::new(lower_cation, *lower), &salt_anion, p_t)?,
+            );
+            let rtype = match products.len() {
+                1 => ReactionType::Combination,
+                _ => ReactionType::Exchange,
+            };
+            return Ok((products, rtype, None));
+        }
+        if me_name == salt_me_name {
+            let reason = "Metal doesn't displace itself from its salt";
+            return Ok((vec![], ReactionType::None, Some(reason)));
+        }
+
+        let (_, salt_me_potential) =
+            get_standard_potential(&salt_me_name, Some(salt_cation.charge))
+                .or(get_standard_potential(&salt_me_name, None))
+                .ok_or("Unknown potential of the metal")?;
+        if me_potential >= salt_me_potential {
+            let reason = "Metal is less active than the one of the salt";
+            return Ok((vec![], ReactionType::None, Some(reason)));
         }
+
+        let substance_me = Substance::from_elements(
+            HashMap::from([(salt_me_name, SB::new(salt_me.element, 1, 0))]),
+            p_t,
+        )?;
+
+        Ok((
+            vec![substance_salt, substance_me],
+            ReactionType::Substition,
+            None,
+        ))
+    }
+
+    // Amphoteric metal + alkali + water = complex salt + H₂: 2Al + 2NaOH + 6H₂O = 2Na[Al(OH)₄] + 3H₂,
+    // base of metal in its lower state + O₂ + water: 4Fe(OH)₂ + O₂ + 2H₂O = 4Fe(OH)₃
+    fn reaction_simple_base_water(
+        reagents: &Vec<Substance>,
+        _amounts: Option<&[u8]>,
+        _conditions: &Conditions,
+        p_t: &PeriodicTable,
+    ) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str> {
+        let simple = get_class_from_reagents(reagents, SC::Simple).ok_or("There is no metal")?;
+        let base = get_class_from_reagents(reagents, SC::Base).ok_or("There is no base")?;
+        let water = get_class_from_reagents(reagents, SC::Oxide).ok_or("There is no water")?;
+        if !is_composition(&water.blocks(), "H2O", p_t) {
+            return Err("Unknown class of reaction");
+        }
+
+        let (base_cation, oh) = get_exchange_ions(base, p_t)?;
+        let (name, sb) = simple
+            .blocks()
+            .into_iter()
+            .next()
+            .ok_or("Unknown substance")?;
+        if name == "O" {
+            let (me_name, me) = base_cation
+                .blocks
+                .iter()
+                .next()
+                .filter(|_| base_cation.blocks.len() == 1)
+                .ok_or("Only bases of metals are oxidised")?;
+            let next = match me
+                .element
+                .valencies
+                .iter()
+                .find(|v| **v as i8 > base_cation.charge)
+            {
+                Some(next) => *next as i8,
+                None => {
+                    let reason = "Metal of the base is in its highest state";
+                    return Ok((vec![], ReactionType::None, Some(reason)));
+                }
+            };
+            let cation = Ion::new(
+                HashMap::from([(me_name.clone(), SB::new(me.element.clone(), 1, next))]),
+                next,
+            );
+            return Ok((
+                vec![get_salt(&cation, &oh, p_t)?],
+                ReactionType::Combination,
+                None,
+            ));
+        }
+
+        if !AMPHOTERIC_METALLS.contains(&name.as_str()) {
+            let reason = "Only amphoteric metals react with alkalis";
+            return Ok((vec![], ReactionType::None, Some(reason)));
+        }
+        if base.solubility(p_t) != Some(Solubility::Soluble) {
+            let reason = "Metals react only with alkali solutions";
+            return Ok((vec![], ReactionType::None, Some(reason)));
+        }
+
+        // hydroxo complexes in solution: Al(OH)₄⁻, Zn(OH)₄²⁻
+        let (me_oxydation, _) =
+            get_standard_potential(&name, None).ok_or("Unknown potential of the metal")?;
+        let mut blocks = HashMap::from([(name, SB::new(sb.element, 1, 0))]);
+        for (name, sb) in &oh.blocks {
+            blocks.insert(name.clone(), SB::new(sb.element.clone(), sb.index * 4, 0));
+        }
+        let residue = Ion::new(blocks, me_oxydation - 4);
+
+        Ok((
+            vec![
+                get_salt(&base_cation, &residue, p_t)?,
+                Substance::from_string("H2", p_t)?,
+            ],
+            ReactionType::Substition,
+            None,
+        ))
+    }
+
+    // Oxidiser + reducer in acidic medium, every cation gives salt with the acid:
+    // 2KMnO₄ + 5Na₂SO₃ + 3H₂SO₄ = 2MnSO₄ + 5Na₂SO₄ + K₂SO₄ + 3H₂O,
+    // the acid may be the reducer itself: 2KMnO₄ + 16HCl = 2MnCl₂ + 5Cl₂ + 2KCl + 8H₂O
+    fn reaction_redox_acidic(
+        reagents: &Vec<Substance>,
+        _amounts: Option<&[u8]>,
+        _conditions: &Conditions,
+        p_t: &PeriodicTable,
+    ) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str> {
+        let (oxidiser, reducer, residue) = get_redox_agents(reagents.as_slice(), p_t)?;
+        let Some(residue) = residue else {
+            let reason = "Weak reducing acid can't be the medium";
+            return Ok((vec![], ReactionType::None, Some(reason)));
+        };
+        let h = Ion::from_string("H", 1, p_t)?;
+
+        let mut products = Vec::new();
+        if oxidiser.partner != h {
+            push_unique(&mut products, get_salt(&oxidiser.partner, &residue, p_t)?);
+        }
+        push_unique(&mut products, get_salt(&oxidiser.product, &residue, p_t)?);
+        match reducer.product.charge {
+            // I⁻ gives I₂, its cation stays with the acid
+            0 => {
+                push_unique(
+                    &mut products,
+                    Substance::from_elements(reducer.product.blocks, p_t)?,
+                );
+                if reducer.partner != h {
+                    push_unique(&mut products, get_salt(&reducer.partner, &residue, p_t)?);
+                }
+            }
+            // SO₃²⁻ gives SO₄²⁻ with the same cation
+            c if c < 0 => {
+                push_unique(
+                    &mut products,
+                    get_salt(&reducer.partner, &reducer.product, p_t)?,
+                );
+            }
+            // Fe²⁺ gives Fe³⁺, which takes the acid residue
+            _ => {
+                if reducer.partner != residue {
+                    return Err("Anion of the reducer has to be the one of the acid");
+                }
+                push_unique(&mut products, get_salt(&reducer.product, &residue, p_t)?);
+            }
+        }
+        push_unique(&mut products, Substance::from_string("H2O", p_t)?);
+
+        balance(reagents, &products)?;
+        Ok((products, ReactionType::Exchange, None))
+    }
+
+    // Thermal decomposition: hydroxides, carbonates, nitrates, ammonium salts
+    // and the ones which decompose in their own way (KMnO₄, KClO₃, H₂O₂)
+    fn reaction_decomposition(
+        reagents: &Vec<Substance>,
+        _amounts: Option<&[u8]>,
+        conditions: &Conditions,
+        p_t: &PeriodicTable,
+    ) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str> {
+        let heating = conditions.is_heated();
+        let rtype = ReactionType::Decomposition;
+        let [substance] = reagents.as_slice() else {
+            return Err("Only one substance decomposes");
+        };
+        if !heating {
+            return Ok((vec![], ReactionType::None, Some("Heating is required")));
+        }
+
+        let sbs = substance.blocks();
+        if let Some((_, t, products)) = DECOMPOSITIONS
+            .iter()
+            .find(|(formula, _, _)| is_composition(&sbs, formula, p_t))
+        {
+            if conditions
+                .temperature
+                .is_some_and(|temperature| temperature.celsius() < *t)
+            {
+                let reason = "Temperature is too low for decomposition";
+                return Ok((vec![], ReactionType::None, Some(reason)));
+            }
+            let mut res = Vec::new();
+            for product in products.iter() {
+                res.push(Substance::from_string(product, p_t)?);
+            }
+            return Ok((res, rtype, None));
+        }
+
+        let no_decomposition = Ok((
+            vec![],
+            ReactionType::None,
+            Some("Substance doesn't decompose when heated"),
+        ));
+        let oxygen = Substance::from_string("O2", p_t)?;
+        let water = Substance::from_string("H2O", p_t)?;
+        let oxide_ion = Ion::from_string("O", -2, p_t)?;
+
+        let products = match substance.class {
+            // NH₄OH = NH₃ + H₂O
+            SC::Base if substance.me.is_empty() => {
+                get_stable_products(vec![substance.clone()], p_t)?
+            }
+            // only insoluble hydroxides: Cu(OH)₂ = CuO + H₂O
+            SC::Base => {
+                if substance.solubility(p_t) == Some(Solubility::Soluble) {
+                    return no_decomposition;
+                }
+                let (cation, _) = get_exchange_ions(substance, p_t)?;
+                vec![get_salt(&cation, &oxide_ion, p_t)?, water]
+            }
+            // NH₄Cl = NH₃ + HCl, (NH₄)₂CO₃ = 2NH₃ + CO₂ + H₂O
+            SC::Salt if substance.me.is_empty() => {
+                let (_, anion) = get_exchange_ions(substance, p_t)?;
+                let h = Ion::from_string("H", 1, p_t)?;
+                let acid = get_salt(&h, &anion, p_t)?;
+                let ammonia = Substance::from_string("NH3", p_t)?;
+                get_stable_products(vec![ammonia, acid], p_t)?
+            }
+            SC::Salt => {
+                let (cation, anion) = get_exchange_ions(substance, p_t)?;
+                let (me_name, _) = cation.blocks.iter().next().ok_or("Unknown salt")?;
+                let me_name = me_name.as_str();
+                match (anion.formula(p_t).as_str(), anion.charge) {
+                    // CaCO₃ = CaO + CO₂, alkali metals' carbonates melt
+                    ("CO3", -2) => {
+                        if ACTIVE_METALLS.contains(&me_name)
+                            && cation.charge == 1
+                            && me_name != "Li"
+                        {
+                            return no_decomposition;
+                        }
+                        let co2 = Substance::from_string("CO2", p_t)?;
+                        vec![get_salt(&cation, &oxide_ion, p_t)?, co2]
+                    }
+                    // 2NaHCO₃ = Na₂CO₃ + CO₂ + H₂O
+                    ("HCO3", -1) => {
+                        let carbonate = Ion::from_string("CO3", -2, p_t)?;
+                        let h2co3 = Substance::from_string("H2CO3", p_t)?;
+                        get_stable_products(vec![get_salt(&cation, &carbonate, p_t)?, h2co3], p_t)?
+                    }
+                    // nitrates along the activity series: KNO₂ + O₂, CuO + NO₂ + O₂, Ag + NO₂ + O₂
+                    ("NO3", -1) => {
+                        let no2 = Substance::from_string("NO2", p_t)?;
+                        if ACTIVE_METALLS.contains(&me_name) && me_name != "Li" {
+                            let nitrite = Ion::from_string("NO2", -1, p_t)?;
+                            vec![get_salt(&cation, &nitrite, p_t)?, oxygen]
+                        } else if NON_ACTIVE_METALLS.contains(&me_name) && me_name != "Cu" {
+                            let sb = &cation.blocks[me_name];
+                            let me = HashMap::from([(
+                                me_name.to_string(),
+                                SB::new(sb.element.clone(), 1, 0),
+                            )]);
+                            vec![Substance::from_elements(me, p_t)?, no2, oxygen]
+                        } else {
+                            vec![get_salt(&cation, &oxide_ion, p_t)?, no2, oxygen]
+                        }
+                    }
+                    _ => return no_decomposition,
+                }
+            }
+            _ => return no_decomposition,
+        };
+
+        Ok((products, rtype, None))
+    }
+
+    // Neutralisation: acid + base = salt + water,
+    // acid salts for the lack of base and basic salts for the lack of acid
+    fn reaction_acid_base(
+        reagents: &Vec<Substance>,
+        amounts: Option<&[u8]>,
+        _conditions: &Conditions,
+        p_t: &PeriodicTable,
+    ) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str> {
+        let acid_i = get_class_position(reagents, SC::Acid).ok_or("There is no acid")?;
+        let base_i = get_class_position(reagents, SC::Base).ok_or("There is no base")?;
+        let amounts = amounts.map(|a| (a[acid_i] as u16, a[base_i] as u16));
+
+        let substance_salt =
+            get_neutralisation_salt(&reagents[acid_i], &reagents[base_i], amounts, p_t)?;
+        let substance_water = Substance::from_string("H2O", p_t)?;
+
+        Ok((
+            vec![substance_salt, substance_water],
+            ReactionType::Exchange,
+            None,
+        ))
+    }
+
+    // Salt + salt, salt + acid and salt + base exchange their ions,
+    // but only when a precipitate, a gas or a weak electrolyte forms
+    fn reaction_exchange(
+        reagents: &Vec<Substance>,
+        _amounts: Option<&[u8]>,
+        _conditions: &Conditions,
+        p_t: &PeriodicTable,
+    ) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str> {
+        let (first, second) = (&reagents[0], &reagents[1]);
+        let acid_i = get_class_position(reagents, SC::Acid);
+
+        // only acids dissolve insoluble salts: CaCO₃ + 2HCl
+        for reagent in reagents {
+            let insoluble = matches!(
+                reagent.solubility(p_t),
+                Some(Solubility::Insoluble | Solubility::NotExists | Solubility::Decomposes)
+            );
+            if reagent.class == SC::Acid && reagent.solubility(p_t) == Some(Solubility::Insoluble) {
+                let reason = "Insoluble acids don't react with salts";
+                return Ok((vec![], ReactionType::None, Some(reason)));
+            }
+            if insoluble && acid_i.is_none() {
+                let reason = "Salts and bases react with each other only in solution";
+                return Ok((vec![], ReactionType::None, Some(reason)));
+            }
+        }
+
+        let (first_cation, first_anion) = get_exchange_ions(first, p_t)?;
+        let (second_cation, second_anion) = get_exchange_ions(second, p_t)?;
+        // NH₄Cl + NH₄OH give the same substances back
+        if first_cation == second_cation || first_anion == second_anion {
+            let reason = "No ion exchange";
+            return Ok((vec![], ReactionType::None, Some(reason)));
+        }
+        let products = vec![
+            get_salt(&first_cation, &second_anion, p_t)?,
+            get_salt(&second_cation, &first_anion, p_t)?,
+        ];
+
+        // a weaker acid doesn't displace a stronger one, unless its salt doesn't dissolve
+        // in that acid: CuSO₄ + H₂S = CuS + H₂SO₄, but FeCl₂ + H₂S ↛
+        if let Some(i) = acid_i {
+            let pka = |acid: &Substance| {
+                acid.acid_properties(p_t)
+                    .and_then(|props| props.pka.first().copied())
+            };
+            if let (Some(reagent_pka), Some(product_pka)) = (pka(&reagents[i]), pka(&products[i])) {
+                let salt = products[1 - i].blocks();
+                let stays = ACID_INSOLUBLE
+                    .iter()
+                    .any(|formula| is_composition(&salt, formula, p_t));
+                // strong acids displace each other by the precipitate: BaCl₂ + H₂SO₄
+                if reagent_pka > product_pka && reagent_pka >= 0.0 && !stays {
+                    let reason = match products[1 - i].solubility(p_t) {
+                        Some(Solubility::Insoluble) => {
+                            "Precipitate dissolves in the acid which forms"
+                        }
+                        _ => "Weaker acid doesn't displace a stronger one",
+                    };
+                    return Ok((vec![], ReactionType::None, Some(reason)));
+                }
+            }
+        }
+
+        let products = get_stable_products(products, p_t)?;
+        if products
+            .iter()
+            .any(|p| p.solubility(p_t) == Some(Solubility::Decomposes))
+        {
+            let reason = "Salt which is hydrolysed by water can't be the product";
+            return Ok((vec![], ReactionType::None, Some(reason)));
+        }
+        let water = Substance::from_string("H2O", p_t)?;
+        let goes = products.iter().any(|p| {
+            matches!(
+                p.solubility(p_t),
+                Some(Solubility::Insoluble | Solubility::SlightlySoluble)
+            ) || p.is_gas(p_t)
+                || *p == water
+                || p.dissociation(p_t)
+                    .is_some_and(|d| d.electrolyte == Strength::Weak)
+        });
+
+        match goes {
+            true => Ok((products, ReactionType::Exchange, None)),
+            false => {
+                let reason = "Neither a precipitate, nor a gas, nor a weak electrolyte forms";
+                Ok((vec![], ReactionType::None, Some(reason)))
+            }
+        }
+    }
+
+    // Basic or amphoteric oxide + acid = salt + water
+    fn reaction_oxide_acid(
+        reagents: &Vec<Substance>,
+        _amounts: Option<&[u8]>,
+        _conditions: &Conditions,
+        p_t: &PeriodicTable,
+    ) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str> {
+        let oxide = get_class_from_reagents(reagents, SC::Oxide).ok_or("There is no oxide")?;
+        let acid = get_class_from_reagents(reagents, SC::Acid).ok_or("There is no acid")?;
+
+        let cation = match oxide.oxide_cation() {
+            Some(cation) => cation,
+            None => {
+                let reason = "Acidic oxides don't react with acids";
+                return Ok((vec![], ReactionType::None, Some(reason)));
+            }
+        };
+        let residue = acid.acid_properties(p_t).ok_or("Unknown acid")?.residue;
+        let substance_water = Substance::from_string("H2O", p_t)?;
+
+        if let Some((_, charge)) = OXIDISING_OXIDES
+            .iter()
+            .find(|(formula, _)| is_composition(&oxide.blocks(), formula, p_t))
+        {
+            let Some(product) = get_redox_product(&REDUCERS, &residue, p_t) else {
+                let reason = "Oxidising oxides react only with reducing acids";
+                return Ok((vec![], ReactionType::None, Some(reason)));
+            };
+            let reduced = Ion::new(cation.blocks.clone(), *charge);
+            // Cl⁻ gives Cl₂ and the rest of the acid takes the reduced cation
+            let products = match product.charge {
+                0 => vec![
+                    get_salt(&reduced, &residue, p_t)?,
+                    Substance::from_elements(product.blocks, p_t)?,
+                    substance_water,
+                ],
+                _ => vec![get_salt(&reduced, &product, p_t)?, substance_water],
+            };
+            balance(reagents, &products)?;
+            return Ok((products, ReactionType::Exchange, None));
+        }
+
+        let substance_salt = get_salt(&cation, &residue, p_t)?;
+
+        Ok((
+            vec![substance_salt, substance_water],
+            ReactionType::Exchange,
+            None,
+        ))
+    }
+
+    // Acidic oxide + base = salt + water, like its acid does: CO₂ + 2NaOH = Na₂CO₃ + H₂O,
+    // acid salt for the excess of oxide: CO₂ + NaOH = NaHCO₃.
+    // Amphoteric oxide + alkali melt = salt + water: Al₂O₃ + 2NaOH = 2NaAlO₂ + H₂O,
+    // in solution it gives the hydroxo complex: Al₂O₃ + 2NaOH + 3H₂O = 2Na[Al(OH)₄]
+    fn reaction_oxide_base(
+        reagents: &Vec<Substance>,
+        amounts: Option<&[u8]>,
+        conditions: &Conditions,
+        p_t: &PeriodicTable,
+    ) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str> {
+        let water_i = reagents
+            .iter()
+            .position(|r| is_composition(&r.blocks(), "H2O", p_t))
+            .filter(|_| reagents.len() == 3);
+        let oxide_i = (0..reagents.len())
+            .find(|i| reagents[*i].class == SC::Oxide && Some(*i) != water_i)
+            .ok_or("There is no oxide")?;
+        let base_i = get_class_position(reagents, SC::Base).ok_or("There is no base")?;
+        let (oxide, base) = (&reagents[oxide_i], &reagents[base_i]);
+        let two_acids = OXIDE_WATER
+            .iter()
+            .find(|(formula, _)| is_composition(&oxide.blocks(), formula, p_t))
+            .map(|(_, acids)| acids);
+
+        let substance_salt = match (oxide.oxide_kind(), base.solubility(p_t)) {
+            (_, Some(Solubility::Insoluble)) => {
+                let reason = "Insoluble bases don't react with oxides";
+                return Ok((vec![], ReactionType::None, Some(reason)));
+            }
+            (Some(OxideKind::Amphoteric), _) if water_i.is_some() && !base.me.is_empty() => {
+                if conditions.melt {
+                    let reason = "There is no water in the melt";
+                    return Ok((vec![], ReactionType::None, Some(reason)));
+                }
+                let (cation, oh) = get_exchange_ions(base, p_t)?;
+                let me = oxide.oxide_cation().ok_or("Unknown oxide")?;
+                // Al(OH)₄⁻, Zn(OH)₄²⁻
+                let mut blocks = me.blocks.clone();
+                for (name, sb) in &oh.blocks {
+                    blocks.insert(name.clone(), SB::new(sb.element.clone(), sb.index * 4, 0));
+                }
+                let complex = Ion::new(blocks, me.charge - 4);
+                return Ok((
+                    vec![get_salt(&cation, &complex, p_t)?],
+                    ReactionType::Combination,
+                    None,
+                ));
+            }
+            _ if water_i.is_some() => {
+                let reason = "Only amphoteric oxides take water from alkali solutions";
+                return Ok((vec![], ReactionType::None, Some(reason)));
+            }
+            // 2NO₂ + 2NaOH = NaNO₃ + NaNO₂ + H₂O
+            (Some(OxideKind::Acidic), _) if two_acids.is_some() => {
+                let mut products = Vec::new();
+                for acid in two_acids.into_iter().flatten() {
+                    let acid = Substance::from_string(acid, p_t)?;
+                    products.push(get_neutralisation_salt(&acid, base, None, p_t)?);
+                }
+                products.push(Substance::from_string("H2O", p_t)?);
+                return Ok((products, ReactionType::Exchange, None));
+            }
+            (Some(OxideKind::Acidic), _) => {
+                let acid = oxide.oxide_acid(p_t).ok_or("Oxide has no acid")?;
+                // P₂O₅ gives 2H₃PO₄
+                let (name, sb) = oxide
+                    .blocks()
+                    .into_iter()
+                    .find(|(name, _)| name != "O")
+                    .ok_or("Unknown oxide")?;
+                let acid_per_oxide = (sb.index / acid.blocks()[&name].index) as u16;
+                let amounts =
+                    amounts.map(|a| (a[oxide_i] as u16 * acid_per_oxide, a[base_i] as u16));
+                get_neutralisation_salt(&acid, base, amounts, p_t)?
+            }
+            (Some(OxideKind::Amphoteric), _) if !base.me.is_empty() => {
+                if !conditions.melt && !conditions.is_heated() {
+                    let reason = "Amphoteric oxides react with alkali melts or with water";
+                    return Ok((vec![], ReactionType::None, Some(reason)));
+                }
+                let residue = oxide.oxide_residue(p_t).ok_or("Unknown oxide")?;
+                let cation = base
+                    .ions(p_t)
+                    .and_then(|ions| ions.into_iter().find(|(ion, _)| ion.charge > 0))
+                    .ok_or("Unknown base")?
+                    .0;
+                get_salt(&cation, &residue, p_t)?
+            }
+            _ => {
+                let reason = "Only acidic and amphoteric oxides react with bases";
+                return Ok((vec![], ReactionType::None, Some(reason)));
+            }
+        };
+
+        let products = get_products_with_water(reagents, substance_salt, p_t)?;
+        Ok((products, ReactionType::Exchange, None))
+    }
+
+    // Basic oxide + acidic oxide = salt: CaO + SO₃ = CaSO₄,
+    // amphoteric oxide is acidic for the basic one and basic for the acidic one
+    fn reaction_oxide_oxide(
+        reagents: &Vec<Substance>,
+        _amounts: Option<&[u8]>,
+        _conditions: &Conditions,
+        p_t: &PeriodicTable,
+    ) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str> {
+        let order = |oxide: &Substance| match oxide.oxide_kind() {
+            Some(OxideKind::Basic) => Some(0),
+            Some(OxideKind::Amphoteric) => Some(1),
+            Some(OxideKind::Acidic) => Some(2),
+            _ => None,
+        };
+        let mut oxides = Vec::new();
+        for oxide in reagents {
+            oxides.push((order(oxide), oxide));
+        }
+        oxides.sort_by_key(|(order, _)| *order);
+
+        let (basic, acidic) = match (oxides[0], oxides[1]) {
+            ((Some(b), basic), (Some(a), acidic)) if b < a => (basic, acidic),
+            _ => {
+                let reason = "Oxides of the same character don't react with each other";
+                return Ok((vec![], ReactionType::None, Some(reason)));
+            }
+        };
+
+        let cation = basic.oxide_cation().ok_or("Unknown oxide")?;
+        let Some(residue) = acidic.oxide_residue(p_t) else {
+            let reason = "Oxide has no acid residue of its own";
+            return Ok((vec![], ReactionType::None, Some(reason)));
+        };
+        let substance_salt = get_salt(&cation, &residue, p_t)?;
+        // Al₂(CO₃)₃ and CuCO₃ don't exist
+        if matches!(
+            substance_salt.solubility(p_t),
+            Some(Solubility::NotExists | Solubility::Decomposes)
+        ) {
+            let reason = "Salt which doesn't exist can't be the product";
+            return Ok((vec![], ReactionType::None, Some(reason)));
+        }
+
+        Ok((vec![substance_salt], ReactionType::Combination, None))
+    }
+}
+
+// Acid, base or salt as one kind of cations and one kind of anions: CuSO₄ - Cu²⁺ and SO₄²⁻
+fn get_exchange_ions(
+    substance: &Substance,
+    p_t: &PeriodicTable,
+) -> Result<(Ion, Ion), &'static str> {
+    let ions = substance.ions(p_t).ok_or("Unknown electrolyte")?;
+    let mut cations = ions.iter().filter(|(ion, _)| ion.charge > 0);
+    let mut anions = ions.iter().filter(|(ion, _)| ion.charge < 0);
+    match (cations.next(), cations.next(), anions.next(), anions.next()) {
+        (Some(cation), None, Some(anion), None) => Ok((cation.0.clone(), anion.0.clone())),
+        _ => Err("Only salts with one cation and one anion can exchange ions"),
     }
 }
 
+// Oxidiser, reducer and residue of the acid which gives them the medium
+// The residue of the medium acid is None when the only acids are weak reducers (H₂S):
+// their salts with the oxidiser cations don't stay in the acidic solution
+fn get_redox_agents(
+    reagents: &[Substance],
+    p_t: &PeriodicTable,
+) -> Result<(RedoxAgent, RedoxAgent, Option<Ion>), &'static str> {
+    let mut acids = Vec::new();
+    for acid in reagents.iter().filter(|s| s.class == SC::Acid) {
+        acids.push(acid.acid_properties(p_t).ok_or("Unknown acid")?);
+    }
+    if acids.is_empty() {
+        return Err("There is no acid");
+    }
+    // a non-reducing acid, otherwise a strong one which is the reducer too (HCl)
+    let residue = acids
+        .iter()
+        .find(|acid| get_redox_product(&REDUCERS, &acid.residue, p_t).is_none())
+        .or_else(|| acids.iter().find(|acid| acid.strength == Strength::Strong))
+        .map(|acid| acid.residue.clone());
+    let (oxidiser, reducer) = find_redox_agents(reagents, &OXIDISERS, p_t)?;
+    Ok((oxidiser, reducer, residue))
+}
+
+// The first salt or acid with an ion from the oxidisers table and the first one with a reducer
+fn find_redox_agents(
+    reagents: &[Substance],
+    oxidisers: &[(&str, i8, &str, i8)],
+    p_t: &PeriodicTable,
+) -> Result<(RedoxAgent, RedoxAgent), &'static str> {
+    let h = Ion::from_string("H", 1, p_t)?;
+
+    let mut oxidiser = None;
+    let mut reducer = None;
+    for substance in reagents {
+        let (cation, anion) = match substance.class {
+            SC::Salt => get_exchange_ions(substance, p_t)?,
+            SC::Acid => (
+                h.clone(),
+                substance
+                    .acid_properties(p_t)
+                    .ok_or("Unknown acid")?
+                    .residue,
+            ),
+            _ => continue,
+        };
+        if oxidiser.is_none() {
+            if let Some(product) = get_redox_product(oxidisers, &anion, p_t) {
+                oxidiser = Some(RedoxAgent {
+                    ion: anion,
+                    partner: cation,
+                    product,
+                });
+                continue;
+            }
+        }
+        if reducer.is_none() {
+            if let Some(product) = get_redox_product(&REDUCERS, &anion, p_t) {
+                reducer = Some(RedoxAgent {
+                    ion: anion,
+                    partner: cation,
+                    product,
+                });
+            } else if let Some(product) = get_redox_product(&REDUCERS, &cation, p_t) {
+                reducer = Some(RedoxAgent {
+                    ion: cation,
+                    partner: anion,
+                    product,
+                });
+            }
+        }
+    }
+
+    match (oxidiser, reducer) {
+        (Some(oxidiser), Some(reducer)) if oxidiser.ion != reducer.ion => Ok((oxidiser, reducer)),
+        _ => Err("There are no oxidiser and reducer"),
+    }
+}
+
+fn get_redox_product(
+    table: &[(&str, i8, &str, i8)],
+    ion: &Ion,
+    p_t: &PeriodicTable,
+) -> Option<Ion> {
+    table
+        .iter()
+        .find_map(|(formula, charge, product, product_charge)| {
+            match Ion::from_string(formula, *charge, p_t).ok()? == *ion {
+                true => Ion::from_string(product, *product_charge, p_t).ok(),
+                false => None,
+            }
+        })
+}
+
+// Salts from different ions may be the same: K₂SO₄ from KMnO₄ and from K₂SO₃
+fn push_unique(products: &mut Vec<Substance>, substance: Substance) {
+    let same = |p: &Substance| composition_ratio(&p.blocks(), &substance.blocks()) == Some(1);
+    if !products.iter().any(same) {
+        products.push(substance);
+    }
+}
+
+// Unstable substances are replaced with the ones they decompose to: H₂CO₃ - CO₂ + H₂O
+fn get_stable_products(
+    products: Vec<Substance>,
+    p_t: &PeriodicTable,
+) -> Result<Vec<Substance>, &'static str> {
+    let mut res = Vec::new();
+    for product in products {
+        let sbs = product.blocks();
+        match UNSTABLE
+            .iter()
+            .find(|(formula, _)| is_composition(&sbs, formula, p_t))
+        {
+            Some((_, parts)) => {
+                for part in parts {
+                    let part = Substance::from_string(part, p_t)?;
+                    if !res.contains(&part) {
+                        res.push(part);
+                    }
+                }
+            }
+            None => res.push(product),
+        }
+    }
+    Ok(res)
+}
+
+// H⁺ of one acid molecule and OH⁻ of one base molecule become water,
+// amounts (acid, base) define how many of them
+fn get_neutralisation_salt(
+    acid: &Substance,
+    base: &Substance,
+    amounts: Option<(u16, u16)>,
+    p_t: &PeriodicTable,
+) -> Result<Substance, &'static str> {
+    let basicity = acid.acid_properties(p_t).ok_or("Unknown acid")?.basicity;
+    let h = Ion::from_string("H", 1, p_t)?;
+    let oh = Ion::from_string("OH", -1, p_t)?;
+    let n_oh = base
+        .ions(p_t)
+        .and_then(|ions| ions.into_iter().find(|(ion, _)| *ion == oh))
+        .ok_or("Unknown base")?
+        .1;
+
+    let (h_k, oh_k) = match amounts {
+        Some((n_acid, n_base)) => {
+            let h_total = n_acid * basicity as u16;
+            let oh_total = n_base * n_oh as u16;
+            match h_total >= oh_total {
+                true => (oh_total.div_ceil(n_acid).min(basicity as u16) as u8, n_oh),
+                false => (basicity, h_total.div_ceil(n_base).min(n_oh as u16) as u8),
+            }
+        }
+        None => (basicity, n_oh),
+    };
+
+    let residue = subtract(&acid.blocks(), &h.blocks, h_k).ok_or("Unknown acid")?;
+    let cation = subtract(&base.blocks(), &oh.blocks, oh_k).ok_or("Unknown base")?;
+    get_salt(
+        &Ion::new(cation, oh_k as i8),
+        &Ion::new(residue, -(h_k as i8)),
+        p_t,
+    )
+}
+
+// Cations and anions in the ratio which makes salt neutral
+fn get_salt(cation: &Ion, anion: &Ion, p_t: &PeriodicTable) -> Result<Substance, &'static str> {
+    let (cation_index, anion_index) =
+        calculate_indexes_for_2(cation.charge, anion.charge).ok_or("Uncharged ion")?;
+
+    let mut map: HashMap<String, SB> = HashMap::new();
+    for (ion, n) in [(cation, cation_index), (anion, anion_index)] {
+        for (name, sb) in &ion.blocks {
+            map.entry(name.clone())
+                .or_insert(SB::new(sb.element.clone(), 0, 0))
+                .index += sb.index * n;
+        }
+    }
+    Substance::from_elements(map, p_t)
+}
+
+// Water is a product only when the reaction has H and O for it
+fn get_products_with_water(
+    reagents: &[Substance],
+    salt: Substance,
+    p_t: &PeriodicTable,
+) -> Result<Vec<Substance>, &'static str> {
+    let products = vec![salt.clone(), Substance::from_string("H2O", p_t)?];
+    match balance(reagents, &products) {
+        Ok(_) => Ok(products),
+        Err(_) => Ok(vec![salt]),
+    }
+}
+
+fn get_class_from_reagents(reagents: &Vec<Substance>, class: SC) -> Option<&Substance> {
+    get_class_position(reagents, class).map(|i| &reagents[i])
+}
+
+// Coefficients which make the number of every element the same on both sides
+fn balance(
+    reagents: &[Substance],
+    products: &[Substance],
+) -> Result<(Vec<u8>, Vec<u8>), &'static str> {
+    if products.is_empty() {
+        return Err("There is no reaction");
+    }
+
+    let substances: Vec<HashMap<String, SB>> = reagents
+        .iter()
+        .chain(products.iter())
+        .map(|s| s.blocks())
+        .collect();
+    let mut elements: Vec<&String> = substances.iter().flat_map(|s| s.keys()).collect();
+    elements.sort();
+    elements.dedup();
+
+    // one row for every element: atoms of reagents - atoms of products = 0
+    let mut matrix: Vec<Vec<i64>> = elements
+        .iter()
+        .map(|name| {
+            substances
+                .iter()
+                .enumerate()
+                .map(|(i, s)| {
+                    let index = s.get(*name).map_or(0, |sb| sb.index as i64);
+                    match i < reagents.len() {
+                        true => index,
+                        false => -index,
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    // the electron balance of redox reactions fixes the ratio of the reducer and the oxidiser,
+    // when it can't be written the electrons given have to be equal to the ones taken
+    let same_sign = |x: &Vec<i64>| x.iter().all(|c| c.signum() == x[0].signum());
+    let mut x = redox::multiplier_rows(reagents, products)
+        .and_then(|rows| null_vector(&[matrix.clone(), rows].concat()))
+        .filter(same_sign)
+        .or_else(|| null_vector(&matrix))
+        .or_else(|| {
+            matrix.push(redox::electron_row(reagents, products)?);
+            null_vector(&matrix)
+        })
+        .ok_or("Reaction can't be balanced")?;
+    if x[0] < 0 {
+        x.iter_mut().for_each(|c| *c = -*c);
+    }
+    if x.iter().any(|&c| c <= 0 || c > u8::MAX as i64) {
+        return Err("Reaction can't be balanced");
+    }
+    let x: Vec<u8> = x.iter().map(|&c| c as u8).collect();
+    let (reagents, products) = x.split_at(reagents.len());
+    Ok((reagents.to_vec(), products.to_vec()))
+}
+
+fn get_class_position(reagents: &Vec<Substance>, class: SC) -> Option<usize> {
+    for (i, reagent) in (0..).zip(reagents) {
+        if reagent.class == class {
+            return Some(i);
+        }
+    }
+    None
+}
+
 fn get_simple_me_from_reagents(reagents: &Vec<Substance>) -> (String, Element) {
     let substance = get_substance_from_reagents(reagents, 1, 0).unwrap();
     let reagent_iter = substance.me.iter().next().unwrap();
@@ -283,36 +1615,40 @@ fn get_substance_from_reagents(
     None
 }
 
-fn get_acid_residue(reagents: &Vec<Substance>) -> (HashMap<String, SB>, i8) {
-    let mut map = HashMap::new();
-    let mut acid_residue_valence = 0;
+fn get_acid_residue(reagents: &Vec<Substance>, p_t: &PeriodicTable) -> (HashMap<String, SB>, i8) {
     for reagent in reagents {
-        if reagent.anti_me.len() > 1 && reagent.me.len() == 0 {
-            for (name, block) in reagent.anti_me.iter() {
-                if block.element.charge != 1 {
-                    // != H
-                    map.insert(name.clone(), block.clone());
-                } else {
-                    acid_residue_valence = (block.index as i8) * -1;
-                }
-            }
+        if let Some(acid) = reagent.acid_properties(p_t) {
+            return (acid.residue.blocks, acid.residue.charge);
         }
     }
-    (map, acid_residue_valence)
+    (HashMap::new(), 0)
+}
+
+// E° of Meⁿ⁺/Me couple, the usual couple of the metal for None charge
+fn get_standard_potential(me_name: &str, charge: Option<i8>) -> Option<(i8, f64)> {
+    STANDARD_POTENTIALS
+        .iter()
+        .find(|(name, c, _)| *name == me_name && charge.is_none_or(|charge| charge == *c))
+        .map(|(_, c, e)| (*c, *e))
 }
 
 // Calculation of indexes for two elements.
 // Idea: element_index = LCM(first_valence, second_valence) / element_oxydation
-fn calculate_indexes_for_2(first_oxydation: i8, second_oxydation: i8) -> (u8, u8) {
-    let first_oxydation = first_oxydation.abs() as u8;
-    let second_oxydation = second_oxydation.abs() as u8;
-    let lcm = lcm(first_oxydation, second_oxydation);
-    (lcm / first_oxydation, lcm / second_oxydation)
+// None if either element has no charge to bond with
+fn calculate_indexes_for_2(first_oxydation: i8, second_oxydation: i8) -> Option<(u8, u8)> {
+    let first_oxydation = first_oxydation.unsigned_abs();
+    let second_oxydation = second_oxydation.unsigned_abs();
+    if first_oxydation == 0 || second_oxydation == 0 {
+        return None;
+    }
+    let lcm = lcm(first_oxydation, second_oxydation)?;
+    Some((lcm / first_oxydation, lcm / second_oxydation))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::quantity::Temperature;
 
     #[test]
     fn reaction_me_antime() {
@@ -332,6 +1668,7 @@ mod tests {
             Substance::from_string("Al2O3", &p_t).unwrap().clone(),
             reaction.products.first().unwrap().clone()
         );
+        assert_eq!(Ok((vec![4, 3], vec![2])), reaction.coefficients());
 
         let reaction = Reaction::try_calculate_from(
             vec![
@@ -359,6 +1696,61 @@ mod tests {
             Substance::from_string("NaCl", &p_t).unwrap().clone(),
             reaction.products.first().unwrap().clone()
         );
+
+        for (me, gas) in [("Na", "He"), ("Mg", "Ar")] {
+            let reaction = Reaction::try_calculate_from(
+                vec![
+                    Substance::from_string(me, &p_t).unwrap(),
+                    Substance::from_string(gas, &p_t).unwrap(),
+                ],
+                true,
+            )
+            .unwrap();
+
+            assert_eq!(ReactionType::None, reaction.rtype);
+            assert_eq!(Some("Noble gases don't react with metals"), reaction.reason);
+        }
+    }
+
+    #[test]
+    fn reaction_nonme_nonme() {
+        let p_t = PeriodicTable::new();
+        let reactions = [
+            ("S", "O2", None, true, "S + O2 → SO2"),
+            ("N2", "H2", None, true, "N2 + 3H2 → 2NH3"),
+            ("P", "Cl2", None, true, "2P + 5Cl2 → 2PCl5"),
+            ("P", "Cl2", Some(vec![2, 3]), true, "2P + 3Cl2 → 2PCl3"),
+            ("C", "O2", Some(vec![2, 1]), true, "2C + O2 → 2CO"),
+            ("H2", "F2", None, false, "H2 + F2 → 2HF"),
+            ("C", "O2", None, false, "C + O2 → no reaction"),
+            ("Cl2", "O2", None, true, "Cl2 + O2 → no reaction"),
+            ("Br2", "F2", None, false, "Br2 + 5F2 → 2BrF5"),
+            ("O2", "F2", None, false, "O2 + 2F2 → 2OF2"),
+        ];
+
+        for (first, second, amounts, heating, equation) in reactions {
+            let reagents = vec![
+                Substance::from_string(first, &p_t).unwrap(),
+                Substance::from_string(second, &p_t).unwrap(),
+            ];
+            let reaction = match amounts {
+                Some(amounts) => Reaction::try_calculate_in_ratio(reagents, amounts, heating),
+                None => Reaction::try_calculate_from(reagents, heating),
+            }
+            .unwrap();
+            assert_eq!(equation, reaction.to_string());
+        }
+
+        let reagents = vec![
+            Substance::from_string("H2", &p_t).unwrap(),
+            Substance::from_string("Cl2", &p_t).unwrap(),
+        ];
+        let conditions = Conditions {
+            light: true,
+            ..Default::default()
+        };
+        let reaction = Reaction::try_calculate_with(reagents, conditions).unwrap();
+        assert_eq!("H2 + Cl2 → 2HCl", reaction.to_string());
     }
 
     #[test]
@@ -412,6 +1804,34 @@ mod tests {
             .contains(&Substance::from_string("LiOH", &p_t).unwrap()));
     }
 
+    #[test]
+    fn reaction_water() {
+        let p_t = PeriodicTable::new();
+        let reactions = [
+            ("Na2O", false, "Na2O + H2O → 2NaOH"),
+            ("SO3", false, "SO3 + H2O → H2SO4"),
+            ("P2O5", false, "P2O5 + 3H2O → 2H3PO4"),
+            ("NO2", false, "2NO2 + H2O → HNO3 + HNO2"),
+            ("CaH2", false, "CaH2 + 2H2O → Ca(OH)2 + 2H2↑"),
+            ("Na2O2", false, "Na2O2 + 2H2O → 2NaOH + H2O2"),
+            ("Na2O2", true, "2Na2O2 + 2H2O → 4NaOH + O2↑"),
+            ("CuO", false, "CuO + H2O → no reaction"),
+            ("SiO2", false, "SiO2 + H2O → no reaction"),
+        ];
+
+        for (substance, heating, equation) in reactions {
+            let reaction = Reaction::try_calculate_from(
+                vec![
+                    Substance::from_string(substance, &p_t).unwrap(),
+                    Substance::from_string("H2O", &p_t).unwrap(),
+                ],
+                heating,
+            )
+            .unwrap();
+            assert_eq!(equation, reaction.to_string());
+        }
+    }
+
     #[test]
     fn reaction_me_acid() {
         let p_t = PeriodicTable::new();
@@ -449,5 +1869,520 @@ mod tests {
         assert!(reaction
             .products
             .contains(&Substance::from_string("Al2(SO4)3", &p_t).unwrap()));
+
+        // not every H is replaceable
+        for (acid, salt) in [("H3PO3", "MgHPO3"), ("CH3COOH", "Mg(CH3COO)2")] {
+            let reaction = Reaction::try_calculate_from(
+                vec![
+                    Substance::from_string("Mg", &p_t).unwrap(),
+                    Substance::from_string(acid, &p_t).unwrap(),
+                ],
+                false,
+            )
+            .unwrap();
+
+            let salt = Substance::from_string(salt, &p_t).unwrap();
+            assert!(reaction
+                .products
+                .iter()
+                .any(|p| composition_ratio(&p.blocks(), &salt.blocks()) == Some(1)));
+        }
+    }
+
+    #[test]
+    fn reaction_me_oxidising_acid() {
+        use crate::matter::concentration::Concentration::{Concentrated, Dilute, MassFraction};
+
+        let p_t = PeriodicTable::new();
+        let reactions = [
+            (
+                "Cu",
+                "HNO3",
+                Concentrated,
+                false,
+                "Cu + 4HNO3 → Cu(NO3)2 + 2NO2↑ + 2H2O",
+            ),
+            (
+                "Cu",
+                "HNO3",
+                Dilute,
+                false,
+                "3Cu + 8HNO3 → 3Cu(NO3)2 + 2NO↑ + 4H2O",
+            ),
+            (
+                "Ag",
+                "HNO3",
+                Concentrated,
+                false,
+                "Ag + 2HNO3 → AgNO3 + NO2↑ + H2O",
+            ),
+            (
+                "Fe",
+                "HNO3",
+                Dilute,
+                false,
+                "Fe + 4HNO3 → Fe(NO3)3 + NO↑ + 2H2O",
+            ),
+            (
+                "Mg",
+                "HNO3",
+                Concentrated,
+                false,
+                "4Mg + 10HNO3 → 4Mg(NO3)2 + N2O↑ + 5H2O",
+            ),
+            (
+                "Ca",
+                "HNO3",
+                Dilute,
+                false,
+                "5Ca + 12HNO3 → 5Ca(NO3)2 + N2↑ + 6H2O",
+            ),
+            (
+                "Zn",
+                "HNO3",
+                MassFraction(0.03),
+                false,
+                "4Zn + 10HNO3 → 4Zn(NO3)2 + NH4NO3 + 3H2O",
+            ),
+            (
+                "Cu",
+                "H2SO4",
+                Concentrated,
+                true,
+                "Cu + 2H2SO4 → CuSO4 + SO2↑ + 2H2O",
+            ),
+            (
+                "Zn",
+                "H2SO4",
+                Concentrated,
+                false,
+                "3Zn + 4H2SO4 → 3ZnSO4 + S + 4H2O",
+            ),
+            (
+                "Na",
+                "H2SO4",
+                MassFraction(0.9),
+                false,
+                "8Na + 5H2SO4 → 4Na2SO4 + H2S↑ + 4H2O",
+            ),
+            (
+                "Fe",
+                "H2SO4",
+                Concentrated,
+                true,
+                "2Fe + 6H2SO4 → Fe2(SO4)3 + 3SO2↑ + 6H2O",
+            ),
+            (
+                "Fe",
+                "H2SO4",
+                Concentrated,
+                false,
+                "Fe + H2SO4 → no reaction",
+            ),
+            ("Al", "HNO3", Concentrated, false, "Al + HNO3 → no reaction"),
+            ("Au", "HNO3", Concentrated, true, "Au + HNO3 → no reaction"),
+            ("Zn", "H2SO4", Dilute, false, "Zn + H2SO4 → ZnSO4 + H2↑"),
+        ];
+
+        for (me, acid, concentration, heating, equation) in reactions {
+            let acid = Substance::from_string(acid, &p_t)
+                .unwrap()
+                .with_concentration(concentration);
+            let reaction = Reaction::try_calculate_from(
+                vec![Substance::from_string(me, &p_t).unwrap(), acid],
+                heating,
+            )
+            .unwrap();
+            assert_eq!(equation, reaction.to_string());
+        }
+    }
+
+    #[test]
+    fn reaction_acid_base() {
+        let p_t = PeriodicTable::new();
+        let reactions = [
+            ("HCl", "NaOH", None, "NaCl"),
+            ("H2SO4", "Al(OH)3", None, "Al2(SO4)3"),
+            ("HNO3", "NH4OH", None, "NH4NO3"),
+            ("H2SO4", "NaOH", Some(vec![1, 1]), "NaHSO4"),
+            ("H2SO4", "NaOH", Some(vec![1, 2]), "Na2SO4"),
+            ("H3PO4", "Ca(OH)2", Some(vec![2, 1]), "Ca(H2PO4)2"),
+            ("HCl", "Mg(OH)2", Some(vec![1, 1]), "MgOHCl"),
+            ("HCl", "NaOH", Some(vec![3, 1]), "NaCl"),
+        ];
+
+        for (acid, base, amounts, salt) in reactions {
+            let reagents = vec![
+                Substance::from_string(acid, &p_t).unwrap(),
+                Substance::from_string(base, &p_t).unwrap(),
+            ];
+            let reaction = match amounts {
+                Some(amounts) => Reaction::try_calculate_in_ratio(reagents, amounts, false),
+                None => Reaction::try_calculate_from(reagents, false),
+            }
+            .unwrap();
+
+            assert_eq!(ReactionType::Exchange, reaction.rtype);
+            assert!(reaction
+                .products
+                .contains(&Substance::from_string("H2O", &p_t).unwrap()));
+            let salt = Substance::from_string(salt, &p_t).unwrap();
+            assert!(reaction
+                .products
+                .iter()
+                .any(|p| composition_ratio(&p.blocks(), &salt.blocks()) == Some(1)));
+        }
+
+        // excess of acid gives acid salts
+        let reactions = [
+            ("H3PO4", "NaOH", 0, "H3PO4 + NaOH → NaH2PO4 + H2O"),
+            ("H2SO4", "NaOH", 1, "H2SO4 + 2NaOH → Na2SO4 + 2H2O"),
+            ("HCl", "Mg(OH)2", 1, "HCl + Mg(OH)2 → MgOHCl + H2O"),
+        ];
+        for (acid, base, excess, equation) in reactions {
+            let reagents = vec![
+                Substance::from_string(acid, &p_t).unwrap(),
+                Substance::from_string(base, &p_t).unwrap(),
+            ];
+            let conditions = Conditions {
+                excess: Some(excess),
+                ..Default::default()
+            };
+            let reaction = Reaction::try_calculate_with(reagents, conditions).unwrap();
+            assert_eq!(equation, reaction.to_string());
+        }
+    }
+
+    #[test]
+    fn reaction_oxide() {
+        let p_t = PeriodicTable::new();
+        let reactions = [
+            (
+                "CuO",
+                "H2SO4",
+                None,
+                ReactionType::Exchange,
+                vec!["CuSO4", "H2O"],
+            ),
+            (
+                "Al2O3",
+                "HCl",
+                None,
+                ReactionType::Exchange,
+                vec!["AlCl3", "H2O"],
+            ),
+            (
+                "CO2",
+                "NaOH",
+                None,
+                ReactionType::Exchange,
+                vec!["Na2CO3", "H2O"],
+            ),
+            (
+                "CO2",
+                "NaOH",
+                Some(vec![1, 1]),
+                ReactionType::Exchange,
+                vec!["NaHCO3"],
+            ),
+            (
+                "SO2",
+                "KOH",
+                None,
+                ReactionType::Exchange,
+                vec!["K2SO3", "H2O"],
+            ),
+            (
+                "P2O5",
+                "NaOH",
+                None,
+                ReactionType::Exchange,
+                vec!["Na3PO4", "H2O"],
+            ),
+            (
+                "NO2",
+                "NaOH",
+                None,
+                ReactionType::Exchange,
+                vec!["NaNO3", "NaNO2", "H2O"],
+            ),
+            ("NO2", "CaO", None, ReactionType::None, vec![]),
+            ("Al2O3", "NaOH", None, ReactionType::None, vec![]),
+            ("SO3", "CaO", None, ReactionType::Combination, vec!["CaSO4"]),
+            (
+                "CaO",
+                "SiO2",
+                None,
+                ReactionType::Combination,
+                vec!["CaSiO3"],
+            ),
+            (
+                "Na2O",
+                "Al2O3",
+                None,
+                ReactionType::Combination,
+                vec!["NaAlO2"],
+            ),
+            (
+                "MnO2",
+                "HCl",
+                None,
+                ReactionType::Exchange,
+                vec!["MnCl2", "Cl2", "H2O"],
+            ),
+            ("MnO2", "H2SO4", None, ReactionType::None, vec![]),
+            ("Al2O3", "CO2", None, ReactionType::None, vec![]),
+            ("CuO", "CO2", None, ReactionType::None, vec![]),
+            ("SO3", "HCl", None, ReactionType::None, vec![]),
+            ("CaO", "Na2O", None, ReactionType::None, vec![]),
+        ];
+
+        for (first, second, amounts, rtype, products) in reactions {
+            let reagents = vec![
+                Substance::from_string(first, &p_t).unwrap(),
+                Substance::from_string(second, &p_t).unwrap(),
+            ];
+            let reaction = match amounts {
+                Some(amounts) => Reaction::try_calculate_in_ratio(reagents, amounts, false),
+                None => Reaction::try_calculate_from(reagents, false),
+            }
+            .unwrap();
+
+            assert_eq!(rtype, reaction.rtype, "{first} + {second}");
+            assert_eq!(products.len(), reaction.products.len());
+            for product in products {
+                let product = Substance::from_string(product, &p_t).unwrap();
+                assert!(reaction
+                    .products
+                    .iter()
+                    .any(|p| composition_ratio(&p.blocks(), &product.blocks()) == Some(1)));
+            }
+        }
+
+        // amphoteric oxide gives aluminate in the melt and the hydroxo complex in solution
+        let substances = |formulas: &[&str]| {
+            formulas
+                .iter()
+                .map(|f| Substance::from_string(f, &p_t).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let melt = Conditions {
+            melt: true,
+            ..Default::default()
+        };
+        let reaction = Reaction::try_calculate_with(substances(&["Al2O3", "NaOH"]), melt.clone());
+        assert_eq!(
+            "Al2O3 + 2NaOH → 2NaAlO2 + H2O",
+            reaction.unwrap().to_string()
+        );
+        let solution = substances(&["Al2O3", "NaOH", "H2O"]);
+        let reaction = Reaction::try_calculate_from(solution.clone(), false).unwrap();
+        assert_eq!("Al2O3 + 2NaOH + 3H2O → 2NaAl(OH)4", reaction.to_string());
+        let reaction = Reaction::try_calculate_with(solution, melt).unwrap();
+        assert_eq!(ReactionType::None, reaction.rtype);
+        let reaction =
+            Reaction::try_calculate_from(substances(&["CO2", "NaOH", "H2O"]), false).unwrap();
+        assert_eq!(ReactionType::None, reaction.rtype);
+    }
+
+    #[test]
+    fn reaction_exchange() {
+        let p_t = PeriodicTable::new();
+        let reactions = [
+            ("BaCl2", "Na2SO4", "BaCl2 + Na2SO4 → BaSO4↓ + 2NaCl"),
+            ("CuSO4", "NaOH", "CuSO4 + 2NaOH → Cu(OH)2↓ + Na2SO4"),
+            ("AgNO3", "HCl", "AgNO3 + HCl → AgCl↓ + HNO3"),
+            ("Na2CO3", "HCl", "Na2CO3 + 2HCl → 2NaCl + CO2↑ + H2O"),
+            ("CaCO3", "HNO3", "CaCO3 + 2HNO3 → Ca(NO3)2 + CO2↑ + H2O"),
+            ("NH4Cl", "KOH", "NH4Cl + KOH → NH3↑ + H2O + KCl"),
+            ("CH3COONa", "HCl", "CH3COONa + HCl → NaCl + CH3COOH"),
+            ("NaCl", "KNO3", "NaCl + KNO3 → no reaction"),
+            ("BaSO4", "NaOH", "BaSO4 + NaOH → no reaction"),
+            ("Al2(SO4)3", "Na2CO3", "Al2(SO4)3 + Na2CO3 → no reaction"),
+            ("CuSO4", "H2S", "CuSO4 + H2S → CuS↓ + H2SO4"),
+            ("FeCl2", "H2S", "FeCl2 + H2S → no reaction"),
+            ("BaCl2", "H3PO4", "BaCl2 + H3PO4 → no reaction"),
+            ("AgNO3", "CH3COOH", "AgNO3 + CH3COOH → no reaction"),
+            ("Na2CO3", "H2S", "Na2CO3 + H2S → no reaction"),
+            ("Na2CO3", "H2SiO3", "Na2CO3 + H2SiO3 → no reaction"),
+            ("CaCO3", "H2SiO3", "CaCO3 + H2SiO3 → no reaction"),
+            ("NH4Cl", "NH4OH", "NH4Cl + NH4OH → no reaction"),
+            ("NaCl", "HCl", "NaCl + HCl → no reaction"),
+        ];
+
+        for (first, second, equation) in reactions {
+            let reaction = Reaction::try_calculate_from(
+                vec![
+                    Substance::from_string(first, &p_t).unwrap(),
+                    Substance::from_string(second, &p_t).unwrap(),
+                ],
+                false,
+            )
+            .unwrap();
+
+            assert_eq!(equation, reaction.to_string());
+            assert_eq!(
+                reaction.products.is_empty(),
+                reaction.rtype == ReactionType::None
+            );
+            assert_eq!(reaction.products.is_empty(), reaction.reason.is_some());
+        }
+
+        let hydrolysed = Reaction::try_calculate_from(
+            vec![
+                Substance::from_string("Al2(SO4)3", &p_t).unwrap(),
+                Substance::from_string("Na2CO3", &p_t).unwrap(),
+            ],
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            Some("Salt which is hydrolysed by water can't be the product"),
+            hydrolysed.reason
+        );
+
+        let unbalanced = Reaction {
+            reagents: vec![Substance::from_string("NaCl", &p_t).unwrap()],
+            conditions: Conditions::default(),
+            products: vec![Substance::from_string("NaOH", &p_t).unwrap()],
+            rtype: ReactionType::Exchange,
+            reason: None,
+            reversible: false,
+        };
+        assert_eq!("NaCl → NaOH (not balanced)", unbalanced.to_string());
+    }
+
+    #[test]
+    fn reaction_me_salt() {
+        let p_t = PeriodicTable::new();
+        let reactions = [
+            ("Fe", "CuSO4", "Fe + CuSO4 → FeSO4 + Cu"),
+            ("Cu", "AgNO3", "Cu + 2AgNO3 → Cu(NO3)2 + 2Ag"),
+            ("Al", "CuCl2", "2Al + 3CuCl2 → 2AlCl3 + 3Cu"),
+            ("Zn", "Pb(NO3)2", "Zn + Pb(NO3)2 → Zn(NO3)2 + Pb"),
+            ("Cu", "FeSO4", "Cu + FeSO4 → no reaction"),
+            ("Na", "CuSO4", "Na + CuSO4 → no reaction"),
+            ("Zn", "AgCl", "Zn + AgCl → no reaction"),
+            ("Fe", "FeCl3", "Fe + 2FeCl3 → 3FeCl2"),
+            ("Cu", "FeCl3", "Cu + 2FeCl3 → CuCl2 + 2FeCl2"),
+            ("Fe", "FeSO4", "Fe + FeSO4 → no reaction"),
+        ];
+
+        for (me, salt, equation) in reactions {
+            let reaction = Reaction::try_calculate_from(
+                vec![
+                    Substance::from_string(me, &p_t).unwrap(),
+                    Substance::from_string(salt, &p_t).unwrap(),
+                ],
+                false,
+            )
+            .unwrap();
+            assert_eq!(equation, reaction.to_string());
+        }
+    }
+
+    #[test]
+    fn reaction_multi_reagent() {
+        let p_t = PeriodicTable::new();
+        let reactions = [
+            (
+                vec!["Al", "NaOH", "H2O"],
+                "2Al + 2NaOH + 6H2O → 2NaAl(OH)4 + 3H2↑",
+            ),
+            (
+                vec!["Fe(OH)2", "O2", "H2O"],
+                "4Fe(OH)2 + O2 + 2H2O → 4Fe(OH)3",
+            ),
+            (
+                vec!["KMnO4", "Na2SO3", "H2SO4"],
+                "2KMnO4 + 5Na2SO3 + 3H2SO4 → K2SO4 + 2MnSO4 + 5Na2SO4 + 3H2O",
+            ),
+            (
+                vec!["K2Cr2O7", "KI", "H2SO4"],
+                "K2Cr2O7 + 6KI + 7H2SO4 → 4K2SO4 + Cr2(SO4)3 + 3I2 + 7H2O",
+            ),
+            (
+                vec!["KMnO4", "HCl"],
+                "2KMnO4 + 16HCl → 2KCl + 2MnCl2 + 5Cl2↑ + 8H2O",
+            ),
+            (vec!["KMnO4", "H2S"], "KMnO4 + H2S → no reaction"),
+            (vec!["K2Cr2O7", "H2S"], "K2Cr2O7 + H2S → no reaction"),
+            (
+                vec!["KMnO4", "H2S", "H2SO4"],
+                "2KMnO4 + 5H2S + 3H2SO4 → K2SO4 + 2MnSO4 + 5S + 8H2O",
+            ),
+            (vec!["Fe", "NaOH", "H2O"], "Fe + NaOH + H2O → no reaction"),
+        ];
+
+        for (reagents, equation) in reactions {
+            let reagents = reagents
+                .iter()
+                .map(|r| Substance::from_string(r, &p_t).unwrap())
+                .collect();
+            let reaction = Reaction::try_calculate_from(reagents, false).unwrap();
+            assert_eq!(equation, reaction.to_string());
+        }
+
+        // errors instead of panics
+        let reagents = vec![
+            Substance::from_string("Na", &p_t).unwrap(),
+            Substance::from_string("Cl2", &p_t).unwrap(),
+            Substance::from_string("O2", &p_t).unwrap(),
+        ];
+        assert!(Reaction::try_calculate_from(reagents, true).is_err());
+        assert!(Reaction::try_calculate_from(vec![], true).is_err());
+    }
+
+    #[test]
+    fn reaction_decomposition() {
+        let p_t = PeriodicTable::new();
+        let reactions = [
+            ("Cu(OH)2", "Cu(OH)2 → CuO + H2O"),
+            ("Fe(OH)3", "2Fe(OH)3 → Fe2O3 + 3H2O"),
+            ("CaCO3", "CaCO3 → CaO + CO2↑"),
+            ("NaHCO3", "2NaHCO3 → Na2CO3 + CO2↑ + H2O"),
+            ("KNO3", "2KNO3 → 2KNO2 + O2↑"),
+            ("Cu(NO3)2", "2Cu(NO3)2 → 2CuO + 4NO2↑ + O2↑"),
+            ("AgNO3", "2AgNO3 → 2Ag + 2NO2↑ + O2↑"),
+            ("NH4Cl", "NH4Cl → NH3↑ + HCl"),
+            ("(NH4)2CO3", "(NH4)2CO3 → 2NH3↑ + CO2↑ + H2O"),
+            ("NH4NO3", "NH4NO3 → N2O↑ + 2H2O"),
+            ("KMnO4", "2KMnO4 → K2MnO4 + MnO2 + O2↑"),
+            ("KClO3", "2KClO3 → 2KCl + 3O2↑"),
+            ("H2O2", "2H2O2 → 2H2O + O2↑"),
+            ("Na2CO3", "Na2CO3 → no reaction"),
+            ("NaOH", "NaOH → no reaction"),
+        ];
+
+        for (formula, equation) in reactions {
+            let reagents = vec![Substance::from_string(formula, &p_t).unwrap()];
+            let reaction = Reaction::try_calculate_from(reagents, true).unwrap();
+            assert_eq!(equation, reaction.to_string());
+        }
+
+        let caco3 = vec![Substance::from_string("CaCO3", &p_t).unwrap()];
+        let reaction = Reaction::try_calculate_from(caco3, false).unwrap();
+        assert_eq!(ReactionType::None, reaction.rtype);
+
+        for (temperature, rtype) in [
+            (100.0, ReactionType::None),
+            (250.0, ReactionType::Decomposition),
+        ] {
+            let kmno4 = vec![Substance::from_string("KMnO4", &p_t).unwrap()];
+            let conditions = Conditions {
+                temperature: Some(Temperature::from_celsius(temperature)),
+                ..Default::default()
+            };
+            let reaction = Reaction::try_calculate_with(kmno4, conditions).unwrap();
+            assert_eq!(rtype, reaction.rtype);
+        }
+
+        // wrong numbers of reagents are errors, not panics
+        assert!(Reaction::try_calculate_from(vec![], true).is_err());
+        let many = ["CaCO3", "NaCl", "KNO3", "Fe"]
+            .iter()
+            .map(|f| Substance::from_string(f, &p_t).unwrap())
+            .collect();
+        let _ = Reaction::try_calculate_from(many, true);
     }
 }
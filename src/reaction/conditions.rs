@@ -0,0 +1,83 @@
+use crate::quantity::{Pressure, Temperature, NORMAL_PRESSURE};
+
+// Above it reagents count as heated
+const ROOM_TEMPERATURE: Temperature = Temperature::from_celsius(25.0);
+// Amount of the reagent in excess against 1 mol of the others
+pub const EXCESS_AMOUNT: u8 = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conditions {
+    pub heating: bool,
+    // None - just room temperature or heating
+    pub temperature: Option<Temperature>,
+    pub pressure: Pressure,
+    pub catalyst: Option<String>,
+    pub light: bool,
+    // formula of the solvent, water by default
+    pub solvent: String,
+    // reagents are melted, not dissolved
+    pub melt: bool,
+    // index of the reagent taken in excess: CO₂ + NaOH(excess) = Na₂CO₃ + H₂O
+    pub excess: Option<usize>,
+}
+
+impl Default for Conditions {
+    fn default() -> Self {
+        Self {
+            heating: false,
+            temperature: None,
+            pressure: NORMAL_PRESSURE,
+            catalyst: None,
+            light: false,
+            solvent: "H2O".to_string(),
+            melt: false,
+            excess: None,
+        }
+    }
+}
+
+impl Conditions {
+    pub fn heated() -> Self {
+        Self {
+            heating: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_heated(&self) -> bool {
+        self.heating || self.temperature.is_some_and(|t| t > ROOM_TEMPERATURE)
+    }
+
+    // Amounts in mol which the excess means, 1 mol for each of the others
+    pub fn excess_amounts(&self, reagents: usize) -> Option<Vec<u8>> {
+        let excess = self.excess.filter(|i| *i < reagents)?;
+        let mut amounts = vec![1; reagents];
+        amounts[excess] = EXCESS_AMOUNT;
+        Some(amounts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conditions() {
+        assert!(!Conditions::default().is_heated());
+        assert_eq!(NORMAL_PRESSURE, Conditions::default().pressure);
+        assert_eq!("H2O", Conditions::default().solvent);
+        assert!(Conditions::heated().is_heated());
+        let hot = Conditions {
+            temperature: Some(Temperature::from_celsius(300.0)),
+            ..Default::default()
+        };
+        assert!(hot.is_heated());
+
+        let excess = Conditions {
+            excess: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(Some(vec![1, EXCESS_AMOUNT]), excess.excess_amounts(2));
+        assert_eq!(None, excess.excess_amounts(1));
+    }
+}
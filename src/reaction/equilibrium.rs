@@ -0,0 +1,416 @@
+use super::Reaction;
+use crate::{
+    matter::{gas::GasConditions, solubility::Solubility, substance::Substance},
+    quantity::{Molarity, Temperature, NORMAL_PRESSURE},
+    utils::{format_util::superscript, periodic_table::PeriodicTable},
+};
+
+// Water above it is vapour and takes part in Kp
+const BOILING_POINT_OF_WATER: Temperature = Temperature::from_celsius(100.0);
+const SOLVER_STEPS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    Gas,
+    Aqueous,
+    Liquid,
+    Solid,
+}
+
+// Where the equilibrium moves
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shift {
+    Forward,
+    Backward,
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    // index among reagents and then products, true if it is added
+    Concentration(usize, bool),
+    // true if it rises
+    Pressure(bool),
+    Temperature(bool),
+}
+
+impl Reaction {
+    pub fn reversible(mut self) -> Self {
+        self.reversible = true;
+        self
+    }
+
+    // Reagents and then products
+    pub fn phases(&self, p_t: &PeriodicTable) -> Vec<Phase> {
+        self.reagents
+            .iter()
+            .chain(self.products.iter())
+            .map(|s| self.phase(s, p_t))
+            .collect()
+    }
+
+    // Kc = [NH₃]² / ([N₂][H₂]³): pure solids and liquids aren't in it
+    pub fn kc_expression(&self, p_t: &PeriodicTable) -> Result<String, &'static str> {
+        let exponents = self.exponents(false, p_t)?;
+        Ok(format!(
+            "Kc = {}",
+            self.expression(&exponents, |f| format!("[{}]", f), "", p_t)
+        ))
+    }
+
+    // Kp = p(NH₃)² / (p(N₂)·p(H₂)³): only gases are in it
+    pub fn kp_expression(&self, p_t: &PeriodicTable) -> Result<String, &'static str> {
+        let exponents = self.exponents(true, p_t)?;
+        Ok(format!(
+            "Kp = {}",
+            self.expression(&exponents, |f| format!("p({})", f), "·", p_t)
+        ))
+    }
+
+    // Δn: moles of gaseous products minus the ones of reagents
+    pub fn gas_moles_change(&self, p_t: &PeriodicTable) -> Result<i16, &'static str> {
+        Ok(self.exponents(true, p_t)?.iter().sum())
+    }
+
+    // Kp = Kc(RT)^Δn
+    pub fn kp_from_kc(
+        &self,
+        kc: f64,
+        temperature: Temperature,
+        p_t: &PeriodicTable,
+    ) -> Result<f64, &'static str> {
+        // RT in L·atm/mol, as Kp is in atm
+        let conditions = GasConditions {
+            temperature,
+            pressure: NORMAL_PRESSURE,
+        };
+        let rt = conditions.molar_volume().litres();
+        Ok(kc * rt.powi(self.gas_moles_change(p_t)? as i32))
+    }
+
+    pub fn kc_from_kp(
+        &self,
+        kp: f64,
+        temperature: Temperature,
+        p_t: &PeriodicTable,
+    ) -> Result<f64, &'static str> {
+        Ok(kp / self.kp_from_kc(1.0, temperature, p_t)?)
+    }
+
+    // Q has the form of Kc for any concentrations, of reagents and then products
+    pub fn reaction_quotient(
+        &self,
+        concentrations: &[Molarity],
+        p_t: &PeriodicTable,
+    ) -> Result<f64, &'static str> {
+        let exponents = self.exponents(false, p_t)?;
+        if concentrations.len() != exponents.len() {
+            return Err("Concentration is required for every reagent and product");
+        }
+        Ok(exponents
+            .iter()
+            .zip(concentrations)
+            .map(|(n, c)| c.molarity().powi(*n as i32))
+            .product())
+    }
+
+    // ICE table: every concentration changes by its exponent × x, x is found from Q(x) = Kc
+    pub fn equilibrium_concentrations(
+        &self,
+        initial: &[Molarity],
+        kc: f64,
+        p_t: &PeriodicTable,
+    ) -> Result<Vec<Molarity>, &'static str> {
+        let exponents = self.exponents(false, p_t)?;
+        if initial.len() != exponents.len() {
+            return Err("Concentration is required for every reagent and product");
+        }
+        if kc <= 0.0 || initial.iter().any(|c| c.molarity() < 0.0) {
+            return Err("Kc and concentrations have to be positive");
+        }
+        if exponents.iter().all(|n| *n == 0) {
+            return Err("There is no concentration in Kc");
+        }
+
+        // every concentration in the expression stays above 0
+        let mut low = f64::NEG_INFINITY;
+        let mut high = f64::INFINITY;
+        for (n, c) in exponents.iter().zip(initial) {
+            let limit = -c.molarity() / *n as f64;
+            match n {
+                1.. => low = low.max(limit),
+                ..=-1 => high = high.min(limit),
+                0 => (),
+            }
+        }
+        if low >= high {
+            return Err("There is nothing to react");
+        }
+
+        // ln Q(x) - ln Kc grows with x
+        let f = |x: f64| -> f64 {
+            exponents
+                .iter()
+                .zip(initial)
+                .filter(|(n, _)| **n != 0)
+                .map(|(n, c)| *n as f64 * (c.molarity() + *n as f64 * x).ln())
+                .sum::<f64>()
+                - kc.ln()
+        };
+        // the open side is widened till the root is in
+        let mut step = 1.0;
+        for _ in 0..SOLVER_STEPS {
+            match (low.is_finite(), high.is_finite()) {
+                (false, _) if f(high - step) <= 0.0 => low = high - step,
+                (_, false) if f(low + step) >= 0.0 => high = low + step,
+                (false, _) | (_, false) => step *= 2.0,
+                _ => break,
+            }
+        }
+        if low.is_infinite() || high.is_infinite() {
+            return Err("Equilibrium concentrations can't be found");
+        }
+        for _ in 0..SOLVER_STEPS {
+            let middle = (low + high) / 2.0;
+            match f(middle) < 0.0 {
+                true => low = middle,
+                false => high = middle,
+            }
+        }
+
+        let x = (low + high) / 2.0;
+        Ok(exponents
+            .iter()
+            .zip(initial)
+            .map(|(n, c)| Molarity::from_molarity(c.molarity() + *n as f64 * x))
+            .collect())
+    }
+
+    // Le Chatelier's principle: the equilibrium moves against the change
+    pub fn le_chatelier(&self, change: Change, p_t: &PeriodicTable) -> Result<Shift, &'static str> {
+        let towards = |forward: bool| match forward {
+            true => Shift::Forward,
+            false => Shift::Backward,
+        };
+        match change {
+            Change::Concentration(i, added) => {
+                let exponents = self.exponents(false, p_t)?;
+                let n = exponents.get(i).ok_or("There is no such substance")?;
+                Ok(match n {
+                    0 => Shift::None,
+                    n => towards((*n < 0) == added),
+                })
+            }
+            // to fewer moles of gas when it's compressed
+            Change::Pressure(rises) => Ok(match self.gas_moles_change(p_t)? {
+                0 => Shift::None,
+                n => towards((n < 0) == rises),
+            }),
+            // to the endothermic side when it's heated
+            Change::Temperature(rises) => {
+                let enthalpy = self
+                    .thermodynamics(p_t)
+                    .map_err(|_| "ΔH of the reaction is unknown")?
+                    .enthalpy
+                    .kilojoules();
+                Ok(match enthalpy == 0.0 {
+                    true => Shift::None,
+                    false => towards((enthalpy > 0.0) == rises),
+                })
+            }
+        }
+    }
+
+    fn phase(&self, s: &Substance, p_t: &PeriodicTable) -> Phase {
+        if s.formula(p_t) == "H2O" {
+            return match self.conditions.temperature {
+                Some(t) if t >= BOILING_POINT_OF_WATER => Phase::Gas,
+                _ => Phase::Liquid,
+            };
+        }
+        if s.is_gas(p_t) {
+            return Phase::Gas;
+        }
+        match s.solubility(p_t) {
+            Some(Solubility::Soluble) => Phase::Aqueous,
+            _ => Phase::Solid,
+        }
+    }
+
+    // coefficients in K: negative for reagents, 0 for the ones which aren't in it
+    fn exponents(&self, only_gases: bool, p_t: &PeriodicTable) -> Result<Vec<i16>, &'static str> {
+        if !self.reversible {
+            return Err("Reaction isn't reversible");
+        }
+        let (r_coefs, p_coefs) = self.coefficients()?;
+        let coefs = r_coefs
+            .iter()
+            .map(|k| -(*k as i16))
+            .chain(p_coefs.iter().map(|k| *k as i16));
+        Ok(self
+            .phases(p_t)
+            .into_iter()
+            .zip(coefs)
+            .map(|(phase, k)| match (phase, only_gases) {
+                (Phase::Gas, _) | (Phase::Aqueous, false) => k,
+                _ => 0,
+            })
+            .collect())
+    }
+
+    fn expression(
+        &self,
+        exponents: &[i16],
+        term: fn(&str) -> String,
+        separator: &str,
+        p_t: &PeriodicTable,
+    ) -> String {
+        let substances: Vec<&Substance> =
+            self.reagents.iter().chain(self.products.iter()).collect();
+        let side = |products: bool| {
+            let terms: Vec<String> = substances
+                .iter()
+                .zip(exponents)
+                .filter(|(_, n)| (**n > 0) == products && **n != 0)
+                .map(|(s, n)| {
+                    // powers of 1 aren't written
+                    let power = match n.unsigned_abs() {
+                        1 => String::new(),
+                        n => superscript(n),
+                    };
+                    format!("{}{}", term(&s.formula(p_t)), power)
+                })
+                .collect();
+            (terms.len(), terms.join(separator))
+        };
+        let (n_products, products) = side(true);
+        let (n_reagents, reagents) = side(false);
+        let products = match n_products {
+            0 => "1".to_string(),
+            _ => products,
+        };
+        match n_reagents {
+            0 => products,
+            1 => format!("{} / {}", products, reagents),
+            _ => format!("{} / ({})", products, reagents),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reaction::ReactionType;
+
+    fn reaction(reagents: &[&str], products: &[&str], p_t: &PeriodicTable) -> Reaction {
+        let substances = |formulas: &[&str]| {
+            formulas
+                .iter()
+                .map(|f| Substance::from_string(f, p_t).unwrap())
+                .collect()
+        };
+        Reaction {
+            reagents: substances(reagents),
+            conditions: Default::default(),
+            products: substances(products),
+            rtype: ReactionType::Combination,
+            reason: None,
+            reversible: false,
+        }
+        .reversible()
+    }
+
+    #[test]
+    fn equilibrium_expressions() {
+        let p_t = PeriodicTable::new();
+        let ammonia = reaction(&["N2", "H2"], &["NH3"], &p_t);
+        assert_eq!("N2 + 3H2 ⇌ 2NH3", ammonia.to_string());
+        assert_eq!(
+            "Kc = [NH3]² / ([N2][H2]³)",
+            ammonia.kc_expression(&p_t).unwrap()
+        );
+        assert_eq!(
+            "Kp = p(NH3)² / (p(N2)·p(H2)³)",
+            ammonia.kp_expression(&p_t).unwrap()
+        );
+        assert_eq!(Ok(-2), ammonia.gas_moles_change(&p_t));
+
+        let t = Temperature::from_kelvin(500.0);
+        let kp = ammonia.kp_from_kc(1.0, t, &p_t).unwrap();
+        assert!((kp - 1.0 / 41.0287_f64.powi(2)).abs() < 1e-7);
+        assert!((ammonia.kc_from_kp(kp, t, &p_t).unwrap() - 1.0).abs() < 1e-9);
+
+        assert_eq!(
+            Ok(Shift::Forward),
+            ammonia.le_chatelier(Change::Pressure(true), &p_t)
+        );
+        assert_eq!(
+            Ok(Shift::Backward),
+            ammonia.le_chatelier(Change::Temperature(true), &p_t)
+        );
+        assert_eq!(
+            Ok(Shift::Forward),
+            ammonia.le_chatelier(Change::Concentration(0, true), &p_t)
+        );
+        assert_eq!(
+            Ok(Shift::Backward),
+            ammonia.le_chatelier(Change::Concentration(2, true), &p_t)
+        );
+
+        let limestone = reaction(&["CaCO3"], &["CaO", "CO2"], &p_t);
+        assert_eq!("Kc = [CO2]", limestone.kc_expression(&p_t).unwrap());
+        assert_eq!(
+            Ok(Shift::None),
+            limestone.le_chatelier(Change::Concentration(1, true), &p_t)
+        );
+        assert_eq!(
+            Ok(Shift::Forward),
+            limestone.le_chatelier(Change::Temperature(true), &p_t)
+        );
+
+        let mut irreversible = reaction(&["N2", "H2"], &["NH3"], &p_t);
+        irreversible.reversible = false;
+        assert!(irreversible.kc_expression(&p_t).is_err());
+    }
+
+    #[test]
+    fn equilibrium_ice_table() {
+        let p_t = PeriodicTable::new();
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+        let c = Molarity::from_molarity;
+
+        // N₂ + O₂ ⇌ 2NO with Kc = 4: (2x)² / (1 - x)² = 4, x = 0.5
+        let oxide = reaction(&["N2", "O2"], &["NO"], &p_t);
+        let equilibrium = oxide
+            .equilibrium_concentrations(&[c(1.0), c(1.0), c(0.0)], 4.0, &p_t)
+            .unwrap();
+        assert!(close(0.5, equilibrium[0].molarity()));
+        assert!(close(1.0, equilibrium[2].molarity()));
+        assert!(close(
+            4.0,
+            oxide.reaction_quotient(&equilibrium, &p_t).unwrap()
+        ));
+
+        // from the products side it comes to the same point
+        let equilibrium = oxide
+            .equilibrium_concentrations(&[c(0.5), c(0.5), c(1.0)], 4.0, &p_t)
+            .unwrap();
+        assert!(close(1.0, equilibrium[2].molarity()));
+
+        // only CO₂ is in Kc = [CO₂]
+        let limestone = reaction(&["CaCO3"], &["CaO", "CO2"], &p_t);
+        let equilibrium = limestone
+            .equilibrium_concentrations(&[c(1.0), c(0.0), c(0.0)], 0.5, &p_t)
+            .unwrap();
+        assert!(close(0.5, equilibrium[2].molarity()));
+        assert!(oxide
+            .equilibrium_concentrations(&[c(0.0), c(1.0), c(0.0)], 4.0, &p_t)
+            .is_err());
+
+        // solids only: Kc has nothing to solve for
+        let sulfide = reaction(&["Fe", "S"], &["FeS"], &p_t);
+        assert!(sulfide
+            .equilibrium_concentrations(&[c(1.0), c(1.0), c(0.0)], 0.5, &p_t)
+            .is_err());
+    }
+}
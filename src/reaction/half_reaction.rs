@@ -0,0 +1,461 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::{
+    find_redox_agents, get_salt,
+    ionic::{particles_to_string, push_particle, IonicEquation, Particle, TOO_MANY_PARTICLES},
+    push_unique, Reaction, ReactionType, RedoxAgent,
+};
+use crate::{
+    matter::{
+        ion::Ion,
+        solubility::Solubility,
+        substance::{Substance, SubstanceBlock, SubstanceClass},
+    },
+    utils::{
+        math_util::{gcd, lcm, null_vector},
+        periodic_table::PeriodicTable,
+        redox_table::{BASIC_OXIDISERS, NEUTRAL_OXIDISERS, OXIDISERS},
+    },
+};
+
+// Acid gives H⁺ and alkali OH⁻, the neutral solution has only water
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Medium {
+    Acidic,
+    Neutral,
+    Basic,
+}
+
+// Ion-electron equation: MnO₄⁻ + 8H⁺ + 5e⁻ → Mn²⁺ + 4H₂O
+#[derive(Debug, Clone)]
+pub struct HalfReaction {
+    pub reagents: Vec<(Particle, u8)>,
+    pub products: Vec<(Particle, u8)>,
+    // given away if positive, taken if negative
+    pub electrons: i8,
+}
+
+#[derive(Debug)]
+pub struct HalfReactions {
+    pub medium: Medium,
+    pub reduction: HalfReaction,
+    pub oxidation: HalfReaction,
+    pub net: IonicEquation,
+    pub full: IonicEquation,
+    pub reaction: Reaction,
+}
+
+impl Medium {
+    pub fn of(reagents: &[Substance], p_t: &PeriodicTable) -> Self {
+        let alkali = |s: &Substance| {
+            s.class == SubstanceClass::Base && s.solubility(p_t) == Some(Solubility::Soluble)
+        };
+        if reagents.iter().any(|s| s.class == SubstanceClass::Acid) {
+            Medium::Acidic
+        } else if reagents.iter().any(alkali) {
+            Medium::Basic
+        } else {
+            Medium::Neutral
+        }
+    }
+
+    // MnO₄⁻ gives Mn²⁺, MnO₂ or MnO₄²⁻
+    pub fn oxidisers(&self) -> &'static [(&'static str, i8, &'static str, i8)] {
+        match self {
+            Medium::Acidic => &OXIDISERS,
+            Medium::Neutral => &NEUTRAL_OXIDISERS,
+            Medium::Basic => &BASIC_OXIDISERS,
+        }
+    }
+}
+
+impl fmt::Display for HalfReaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let electrons = match self.electrons.unsigned_abs() {
+            1 => "e⁻".to_string(),
+            n => format!("{}e⁻", n),
+        };
+        let sign = match self.electrons > 0 {
+            true => "−",
+            false => "+",
+        };
+        let p_t = PeriodicTable::new();
+        write!(
+            f,
+            "{} {} {} → {}",
+            particles_to_string(&self.reagents, &p_t),
+            sign,
+            electrons,
+            particles_to_string(&self.products, &p_t)
+        )
+    }
+}
+
+impl HalfReaction {
+    // Atoms are balanced with H₂O and H⁺ or OH⁻ of the medium, the charge - with electrons
+    pub fn new(
+        from: &Ion,
+        to: &Ion,
+        medium: Medium,
+        p_t: &PeriodicTable,
+    ) -> Result<Self, &'static str> {
+        let water = Ion::from_string("H2O", 0, p_t)?;
+        let h = Ion::from_string("H", 1, p_t)?;
+        let oh = Ion::from_string("OH", -1, p_t)?;
+        let helpers = match medium {
+            Medium::Acidic => vec![h],
+            Medium::Basic => vec![oh],
+            // water can only be a reagent: it gives H⁺ or OH⁻
+            Medium::Neutral => vec![h, oh],
+        };
+
+        for helper in helpers {
+            let ions = [from, to, &water, &helper];
+            let Some(x) = half_null_vector(&ions) else {
+                continue;
+            };
+            if x[1] >= 0 || (medium == Medium::Neutral && x[2] < 0) {
+                continue;
+            }
+
+            let mut res = Self {
+                reagents: Vec::new(),
+                products: Vec::new(),
+                // the last column is the electron which is -1 of charge on the left
+                electrons: -x[4] as i8,
+            };
+            for (ion, k) in ions.into_iter().zip(&x) {
+                match k {
+                    1.. => res.reagents.push((particle(ion, p_t), *k as u8)),
+                    ..=-1 => res.products.push((particle(ion, p_t), -k as u8)),
+                    0 => (),
+                }
+            }
+            return Ok(res);
+        }
+        Err("Half-reaction can't be balanced")
+    }
+}
+
+impl HalfReactions {
+    // Oxidiser and reducer salts with the acid, alkali or water which gives the medium:
+    // KMnO₄ + Na₂SO₃ + H₂O → MnO₂ + KOH + Na₂SO₄
+    pub fn calculate(reagents: Vec<Substance>, p_t: &PeriodicTable) -> Result<Self, &'static str> {
+        let medium = Medium::of(&reagents, p_t);
+        let (oxidiser, reducer) = find_redox_agents(&reagents, medium.oxidisers(), p_t)?;
+        let reduction = HalfReaction::new(&oxidiser.ion, &oxidiser.product, medium, p_t)?;
+        let oxidation = HalfReaction::new(&reducer.ion, &reducer.product, medium, p_t)?;
+        let net = combine(&reduction, &oxidation, p_t)?;
+
+        let mut reagents = reagents;
+        let mut products = get_products(&reagents, &oxidiser, &reducer, medium, p_t)?;
+        let water = Particle::Molecule(Substance::from_string("H2O", p_t)?);
+        let water_substance = Substance::from_string("H2O", p_t)?;
+        if net.products.iter().any(|(p, _)| *p == water) {
+            push_unique(&mut products, water_substance);
+        } else if net.reagents.iter().any(|(p, _)| *p == water) {
+            push_unique(&mut reagents, water_substance);
+        }
+
+        let reaction = Reaction {
+            reagents,
+            conditions: Default::default(),
+            products,
+            rtype: ReactionType::Exchange,
+            reason: None,
+            reversible: false,
+        };
+        let full = reaction.ionic_equations(p_t)?.full;
+        Ok(Self {
+            medium,
+            reduction,
+            oxidation,
+            net,
+            full,
+            reaction,
+        })
+    }
+}
+
+// Both half-reactions are multiplied to make the electrons equal,
+// then H⁺ and OH⁻ of one side give water and the same particles on both sides are cancelled
+pub fn combine(
+    reduction: &HalfReaction,
+    oxidation: &HalfReaction,
+    p_t: &PeriodicTable,
+) -> Result<IonicEquation, &'static str> {
+    if reduction.electrons >= 0 || oxidation.electrons <= 0 {
+        return Err("One half-reaction has to take electrons and the other to give them");
+    }
+    let gained = reduction.electrons.unsigned_abs();
+    let lost = oxidation.electrons.unsigned_abs();
+    let electrons = lcm(gained, lost).ok_or(TOO_MANY_PARTICLES)?;
+
+    let mut res = IonicEquation {
+        reagents: Vec::new(),
+        products: Vec::new(),
+    };
+    for (half, k) in [
+        (reduction, electrons / gained),
+        (oxidation, electrons / lost),
+    ] {
+        for (p, n) in &half.reagents {
+            let n = n.checked_mul(k).ok_or(TOO_MANY_PARTICLES)?;
+            push_particle(&mut res.reagents, p.clone(), n)?;
+        }
+        for (p, n) in &half.products {
+            let n = n.checked_mul(k).ok_or(TOO_MANY_PARTICLES)?;
+            push_particle(&mut res.products, p.clone(), n)?;
+        }
+    }
+
+    let h = Particle::Ion(Ion::from_string("H", 1, p_t)?);
+    let oh = Particle::Ion(Ion::from_string("OH", -1, p_t)?);
+    let water = Particle::Molecule(Substance::from_string("H2O", p_t)?);
+    for side in [&mut res.reagents, &mut res.products] {
+        let count = |side: &Vec<(Particle, u8)>, particle: &Particle| {
+            side.iter()
+                .find(|(p, _)| p == particle)
+                .map_or(0, |(_, n)| *n)
+        };
+        let n = count(side, &h).min(count(side, &oh));
+        if n > 0 {
+            for (p, m) in side.iter_mut() {
+                if *p == h || *p == oh {
+                    *m -= n;
+                }
+            }
+            push_particle(side, water.clone(), n)?;
+        }
+    }
+
+    for i in 0..res.reagents.len() {
+        if let Some(j) = res
+            .products
+            .iter()
+            .position(|(p, _)| *p == res.reagents[i].0)
+        {
+            let common = res.reagents[i].1.min(res.products[j].1);
+            res.reagents[i].1 -= common;
+            res.products[j].1 -= common;
+        }
+    }
+    res.reagents.retain(|(_, n)| *n > 0);
+    res.products.retain(|(_, n)| *n > 0);
+
+    let g = res
+        .reagents
+        .iter()
+        .chain(res.products.iter())
+        .fold(0, |g, (_, n)| gcd(g, *n));
+    for (_, n) in res.reagents.iter_mut().chain(res.products.iter_mut()) {
+        *n /= g;
+    }
+    Ok(res)
+}
+
+// Every column is a particle and the electron is the last one,
+// rows are elements and the charge
+fn half_null_vector(ions: &[&Ion]) -> Option<Vec<i64>> {
+    let mut elements: Vec<&String> = ions.iter().flat_map(|ion| ion.blocks.keys()).collect();
+    elements.sort();
+    elements.dedup();
+
+    let index = |blocks: &HashMap<String, SubstanceBlock>, name: &String| {
+        blocks.get(name).map_or(0, |sb| sb.index as i64)
+    };
+    let mut matrix: Vec<Vec<i64>> = elements
+        .iter()
+        .map(|name| {
+            let mut row: Vec<i64> = ions.iter().map(|ion| index(&ion.blocks, name)).collect();
+            row.push(0);
+            row
+        })
+        .collect();
+    let mut charges: Vec<i64> = ions.iter().map(|ion| ion.charge as i64).collect();
+    charges.push(-1);
+    matrix.push(charges);
+
+    let mut x = null_vector(&matrix)?;
+    if x[0] < 0 {
+        x.iter_mut().for_each(|c| *c = -*c);
+    }
+    Some(x)
+}
+
+// Salts keep their cations: K⁺ of KMnO₄ goes to K₂MnO₄ in alkali,
+// to K₂SO₄ in H₂SO₄ and to KOH in water
+fn get_products(
+    reagents: &[Substance],
+    oxidiser: &RedoxAgent,
+    reducer: &RedoxAgent,
+    medium: Medium,
+    p_t: &PeriodicTable,
+) -> Result<Vec<Substance>, &'static str> {
+    let h = Ion::from_string("H", 1, p_t)?;
+    let counter = match medium {
+        Medium::Acidic => {
+            let acid = reagents
+                .iter()
+                .find(|s| s.class == SubstanceClass::Acid)
+                .ok_or("There is no acid")?;
+            acid.acid_properties(p_t).ok_or("Unknown acid")?.residue
+        }
+        _ => Ion::from_string("OH", -1, p_t)?,
+    };
+    let pair = |cation: &Ion| match oxidiser.product.charge < 0 {
+        true => get_salt(cation, &oxidiser.product, p_t),
+        false => get_salt(cation, &counter, p_t),
+    };
+
+    let mut products = Vec::new();
+    if oxidiser.partner != h {
+        push_unique(&mut products, pair(&oxidiser.partner)?);
+    }
+    match oxidiser.product.charge {
+        0 => push_unique(
+            &mut products,
+            Substance::from_elements(oxidiser.product.blocks.clone(), p_t)?,
+        ),
+        c if c > 0 => push_unique(&mut products, get_salt(&oxidiser.product, &counter, p_t)?),
+        _ => (),
+    }
+    match reducer.product.charge {
+        0 => {
+            push_unique(
+                &mut products,
+                Substance::from_elements(reducer.product.blocks.clone(), p_t)?,
+            );
+            if reducer.partner != h {
+                push_unique(&mut products, get_salt(&reducer.partner, &counter, p_t)?);
+            }
+        }
+        c if c < 0 => push_unique(
+            &mut products,
+            get_salt(&reducer.partner, &reducer.product, p_t)?,
+        ),
+        _ => {
+            if reducer.partner != counter {
+                return Err("Anion of the reducer has to be the one of the medium");
+            }
+            push_unique(&mut products, get_salt(&reducer.product, &counter, p_t)?);
+        }
+    }
+
+    // K⁺ of KOH goes with the oxidiser product
+    if medium == Medium::Basic && oxidiser.product.charge < 0 {
+        for base in reagents.iter().filter(|s| s.class == SubstanceClass::Base) {
+            let ions = base.ions(p_t).ok_or("Unknown electrolyte")?;
+            if let Some((cation, _)) = ions.iter().find(|(ion, _)| ion.charge > 0) {
+                push_unique(&mut products, pair(cation)?);
+            }
+        }
+    }
+    Ok(products)
+}
+
+// neutral particles are molecules: H₂O, MnO₂
+fn particle(ion: &Ion, p_t: &PeriodicTable) -> Particle {
+    match ion.charge {
+        0 => match Substance::from_elements(ion.blocks.clone(), p_t) {
+            Ok(s) => Particle::Molecule(s),
+            Err(_) => Particle::Ion(ion.clone()),
+        },
+        _ => Particle::Ion(ion.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn half_reaction() {
+        let p_t = PeriodicTable::new();
+        let ion = |formula: &str, charge: i8| Ion::from_string(formula, charge, &p_t).unwrap();
+        let half_reactions = [
+            (
+                ion("MnO4", -1),
+                ion("Mn", 2),
+                Medium::Acidic,
+                "MnO4⁻ + 8H⁺ + 5e⁻ → Mn²⁺ + 4H2O",
+            ),
+            (
+                ion("MnO4", -1),
+                ion("MnO2", 0),
+                Medium::Neutral,
+                "MnO4⁻ + 2H2O + 3e⁻ → MnO2 + 4OH⁻",
+            ),
+            (
+                ion("MnO4", -1),
+                ion("MnO4", -2),
+                Medium::Basic,
+                "MnO4⁻ + e⁻ → MnO4²⁻",
+            ),
+            (
+                ion("SO3", -2),
+                ion("SO4", -2),
+                Medium::Acidic,
+                "SO3²⁻ + H2O − 2e⁻ → SO4²⁻ + 2H⁺",
+            ),
+            (
+                ion("SO3", -2),
+                ion("SO4", -2),
+                Medium::Neutral,
+                "SO3²⁻ + H2O − 2e⁻ → SO4²⁻ + 2H⁺",
+            ),
+            (
+                ion("SO3", -2),
+                ion("SO4", -2),
+                Medium::Basic,
+                "SO3²⁻ + 2OH⁻ − 2e⁻ → SO4²⁻ + H2O",
+            ),
+        ];
+
+        for (from, to, medium, expected) in half_reactions {
+            let half = HalfReaction::new(&from, &to, medium, &p_t).unwrap();
+            assert_eq!(expected, half.to_string());
+        }
+
+        // 130 particles ×2 don't fit in u8
+        let mut reduction =
+            HalfReaction::new(&ion("MnO4", -1), &ion("Mn", 2), Medium::Acidic, &p_t).unwrap();
+        reduction.reagents[0].1 = 130;
+        let oxidation =
+            HalfReaction::new(&ion("SO3", -2), &ion("SO4", -2), Medium::Acidic, &p_t).unwrap();
+        assert!(combine(&reduction, &oxidation, &p_t).is_err());
+    }
+
+    #[test]
+    fn half_reactions_in_medium() {
+        let p_t = PeriodicTable::new();
+        let reactions = [
+            (
+                ["KMnO4", "Na2SO3", "H2SO4"],
+                "2MnO4⁻ + 6H⁺ + 5SO3²⁻ → 2Mn²⁺ + 3H2O + 5SO4²⁻",
+                "2KMnO4 + 5Na2SO3 + 3H2SO4 → K2SO4 + 2MnSO4 + 5Na2SO4 + 3H2O",
+            ),
+            (
+                ["KMnO4", "Na2SO3", "H2O"],
+                "2MnO4⁻ + H2O + 3SO3²⁻ → 2MnO2 + 2OH⁻ + 3SO4²⁻",
+                "2KMnO4 + 3Na2SO3 + H2O → 2KOH + 2MnO2 + 3Na2SO4",
+            ),
+            (
+                ["KMnO4", "Na2SO3", "KOH"],
+                "2MnO4⁻ + SO3²⁻ + 2OH⁻ → 2MnO4²⁻ + SO4²⁻ + H2O",
+                "2KMnO4 + Na2SO3 + 2KOH → 2K2MnO4 + Na2SO4 + H2O",
+            ),
+        ];
+
+        for (formulas, net, molecular) in reactions {
+            let reagents = formulas
+                .iter()
+                .map(|f| Substance::from_string(f, &p_t).unwrap())
+                .collect();
+            let half_reactions = HalfReactions::calculate(reagents, &p_t).unwrap();
+            assert_eq!(net, half_reactions.net.to_string());
+            assert_eq!(molecular, half_reactions.reaction.to_string());
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use std::fmt;
+
+use super::Reaction;
+use crate::{
+    matter::{
+        acid::Strength,
+        ion::{superscript_charge, Ion},
+        solubility::Solubility,
+        substance::{composition_ratio, Substance},
+    },
+    utils::{math_util::gcd, periodic_table::PeriodicTable},
+};
+
+// Numbers of particles are u8 as coefficients are
+pub const TOO_MANY_PARTICLES: &str = "Too many particles in the equation";
+
+// Undissociated substances stay molecules: precipitates, gases, water, weak electrolytes
+#[derive(Debug, Clone)]
+pub enum Particle {
+    Ion(Ion),
+    Molecule(Substance),
+}
+
+#[derive(Debug, Clone)]
+pub struct IonicEquation {
+    pub reagents: Vec<(Particle, u8)>,
+    pub products: Vec<(Particle, u8)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct IonicEquations {
+    pub full: IonicEquation,
+    pub net: IonicEquation,
+    // ions which are the same on both sides
+    pub spectators: Vec<(Ion, u8)>,
+}
+
+impl PartialEq for Particle {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Particle::Ion(a), Particle::Ion(b)) => a == b,
+            (Particle::Molecule(a), Particle::Molecule(b)) => {
+                composition_ratio(&a.blocks(), &b.blocks()) == Some(1)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Particle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Particle::Ion(ion) => write!(f, "{}", ion),
+            Particle::Molecule(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl fmt::Display for IonicEquation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let p_t = PeriodicTable::new();
+        write!(
+            f,
+            "{} → {}",
+            particles_to_string(&self.reagents, &p_t),
+            particles_to_string(&self.products, &p_t)
+        )
+    }
+}
+
+impl Reaction {
+    // Full ionic equation and the net one without spectator ions
+    pub fn ionic_equations(&self, p_t: &PeriodicTable) -> Result<IonicEquations, &'static str> {
+        let (r_coefs, p_coefs) = self.coefficients()?;
+        let full = IonicEquation {
+            reagents: particles(&self.reagents, &r_coefs, p_t)?,
+            products: particles(&self.products, &p_coefs, p_t)?,
+        };
+
+        let mut net = full.clone();
+        let mut spectators = Vec::new();
+        for i in 0..net.reagents.len() {
+            let (particle, n) = &net.reagents[i];
+            let Particle::Ion(ion) = particle else {
+                continue;
+            };
+            if let Some(j) = net.products.iter().position(|(p, _)| p == particle) {
+                let common = (*n).min(net.products[j].1);
+                spectators.push((ion.clone(), common));
+                net.reagents[i].1 -= common;
+                net.products[j].1 -= common;
+            }
+        }
+        net.reagents.retain(|(_, n)| *n > 0);
+        net.products.retain(|(_, n)| *n > 0);
+        if net.reagents.is_empty() || net.products.is_empty() {
+            return Err("All the ions are spectators - there is no reaction in the solution");
+        }
+
+        let g = net
+            .reagents
+            .iter()
+            .chain(net.products.iter())
+            .fold(0, |g, (_, n)| gcd(g, *n));
+        for (_, n) in net.reagents.iter_mut().chain(net.products.iter_mut()) {
+            *n /= g;
+        }
+
+        Ok(IonicEquations {
+            full,
+            net,
+            spectators,
+        })
+    }
+}
+
+// strong electrolytes are split into ions, equal particles are summed up
+fn particles(
+    substances: &[Substance],
+    coefs: &[u8],
+    p_t: &PeriodicTable,
+) -> Result<Vec<(Particle, u8)>, &'static str> {
+    let mut res: Vec<(Particle, u8)> = Vec::new();
+    for (s, k) in substances.iter().zip(coefs) {
+        let parts = match ions_in_solution(s, p_t) {
+            Some(ions) => ions
+                .into_iter()
+                .map(|(ion, n)| (Particle::Ion(ion), n.checked_mul(*k)))
+                .collect(),
+            None => vec![(Particle::Molecule(s.clone()), Some(*k))],
+        };
+        for (p, n) in parts {
+            push_particle(&mut res, p, n.ok_or(TOO_MANY_PARTICLES)?)?;
+        }
+    }
+    Ok(res)
+}
+
+// equal particles are summed up
+pub fn push_particle(
+    particles: &mut Vec<(Particle, u8)>,
+    particle: Particle,
+    n: u8,
+) -> Result<(), &'static str> {
+    match particles.iter_mut().find(|(other, _)| *other == particle) {
+        Some((_, m)) => *m = m.checked_add(n).ok_or(TOO_MANY_PARTICLES)?,
+        None => particles.push((particle, n)),
+    }
+    Ok(())
+}
+
+// 2H⁺ + SO₄²⁻ + H₂O
+pub fn particles_to_string(particles: &[(Particle, u8)], p_t: &PeriodicTable) -> String {
+    particles
+        .iter()
+        .map(|(p, n)| {
+            let p = match p {
+                Particle::Ion(ion) => {
+                    format!("{}{}", ion.formula(p_t), superscript_charge(ion.charge))
+                }
+                Particle::Molecule(s) => s.formula(p_t),
+            };
+            match n {
+                1 => p,
+                n => format!("{}{}", n, p),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" + ")
+}
+
+// slightly soluble ones are written as molecules too
+fn ions_in_solution(s: &Substance, p_t: &PeriodicTable) -> Option<Vec<(Ion, u8)>> {
+    if s.solubility(p_t) == Some(Solubility::SlightlySoluble) {
+        return None;
+    }
+    let dissociation = s.dissociation(p_t)?;
+    match dissociation.electrolyte {
+        Strength::Strong => Some(dissociation.ions),
+        Strength::Weak => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{reaction::ReactionType, utils::periodic_table::PeriodicTable};
+
+    fn reaction(reagents: &[&str], products: &[&str], p_t: &PeriodicTable) -> Reaction {
+        let substances = |formulas: &[&str]| {
+            formulas
+                .iter()
+                .map(|f| Substance::from_string(f, p_t).unwrap())
+                .collect()
+        };
+        Reaction {
+            reagents: substances(reagents),
+            conditions: Default::default(),
+            products: substances(products),
+            rtype: ReactionType::Exchange,
+            reason: None,
+            reversible: false,
+        }
+    }
+
+    #[test]
+    fn ionic_equations() {
+        let p_t = PeriodicTable::new();
+        let reactions = [
+            (
+                reaction(&["HCl", "NaOH"], &["NaCl", "H2O"], &p_t),
+                "H⁺ + Cl⁻ + Na⁺ + OH⁻ → Na⁺ + Cl⁻ + H2O",
+                "H⁺ + OH⁻ → H2O",
+            ),
+            (
+                reaction(&["BaCl2", "Na2SO4"], &["BaSO4", "NaCl"], &p_t),
+                "Ba²⁺ + 2Cl⁻ + 2Na⁺ + SO4²⁻ → BaSO4 + 2Na⁺ + 2Cl⁻",
+                "Ba²⁺ + SO4²⁻ → BaSO4",
+            ),
+            (
+                reaction(&["CH3COOH", "NaOH"], &["CH3COONa", "H2O"], &p_t),
+                "CH3COOH + Na⁺ + OH⁻ → Na⁺ + CH3COO⁻ + H2O",
+                "CH3COOH + OH⁻ → CH3COO⁻ + H2O",
+            ),
+            (
+                reaction(&["Zn", "H2SO4"], &["ZnSO4", "H2"], &p_t),
+                "Zn + 2H⁺ + SO4²⁻ → Zn²⁺ + SO4²⁻ + H2",
+                "Zn + 2H⁺ → Zn²⁺ + H2",
+            ),
+        ];
+
+        for (reaction, full, net) in reactions {
+            let equations = reaction.ionic_equations(&p_t).unwrap();
+            assert_eq!(full, equations.full.to_string());
+            assert_eq!(net, equations.net.to_string());
+        }
+
+        let equations = reaction(&["BaCl2", "Na2SO4"], &["BaSO4", "NaCl"], &p_t)
+            .ionic_equations(&p_t)
+            .unwrap();
+        let spectators: Vec<String> = equations
+            .spectators
+            .iter()
+            .map(|(ion, n)| format!("{}{}", n, ion))
+            .collect();
+        assert_eq!(vec!["2Cl⁻", "2Na⁺"], spectators);
+
+        let no_reaction = reaction(&["NaCl", "KNO3"], &["NaNO3", "KCl"], &p_t);
+        assert!(no_reaction.ionic_equations(&p_t).is_err());
+    }
+}
@@ -0,0 +1,342 @@
+use std::fmt;
+
+use super::Reaction;
+use crate::{
+    matter::substance::{Substance, SubstanceClass},
+    utils::{format_util::superscript, math_util::lcm, periodic_table::PeriodicTable},
+};
+
+// Atoms of element which give or take electrons: Fe⁰ − 3e⁻ → Fe⁺³ ×4
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElectronTransfer {
+    pub element: String,
+    pub from: i8,
+    pub to: i8,
+    // O₂ takes electrons by 2 atoms
+    pub atoms: u8,
+    pub multiplier: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct Redox {
+    pub transfers: Vec<ElectronTransfer>,
+    pub oxidisers: Vec<Substance>,
+    pub reducers: Vec<Substance>,
+}
+
+impl ElectronTransfer {
+    pub fn electrons(&self) -> u8 {
+        self.atoms * (self.to - self.from).unsigned_abs()
+    }
+
+    pub fn is_oxidation(&self) -> bool {
+        self.to > self.from
+    }
+}
+
+impl fmt::Display for ElectronTransfer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the simple substance is written as a molecule: O2⁰ → 2O⁻², 2Cl⁻¹ → Cl2⁰
+        let molecule = format!("{}{}", self.element, self.atoms);
+        let atoms = format!("{}{}", self.atoms, self.element);
+        let (from, to) = match (self.atoms, self.from) {
+            (1, _) => (self.element.clone(), self.element.clone()),
+            (_, 0) => (molecule, atoms),
+            _ => (atoms, molecule),
+        };
+        let sign = match self.is_oxidation() {
+            true => "−",
+            false => "+",
+        };
+        write!(
+            f,
+            "{}{} {} {}e⁻ → {}{} ×{}",
+            from,
+            superscript_state(self.from),
+            sign,
+            self.electrons(),
+            to,
+            superscript_state(self.to),
+            self.multiplier
+        )
+    }
+}
+
+impl fmt::Display for Redox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self.transfers.iter().map(|t| t.to_string()).collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl Reaction {
+    // None when no element changes its oxidation state
+    pub fn redox(&self, p_t: &PeriodicTable) -> Option<Redox> {
+        let transfers = electron_balance(&self.reagents, &self.products)?;
+
+        // the ones which have the element in its state before the transfer
+        let agents = |oxidation: bool| {
+            let mut res: Vec<Substance> = Vec::new();
+            for t in transfers.iter().filter(|t| t.is_oxidation() == oxidation) {
+                for r in &self.reagents {
+                    let has = r
+                        .blocks()
+                        .get(&t.element)
+                        .is_some_and(|sb| sb.oxidation_state == t.from);
+                    if has && !res.iter().any(|s| s.formula(p_t) == r.formula(p_t)) {
+                        res.push(r.clone());
+                    }
+                }
+            }
+            res
+        };
+
+        Some(Redox {
+            oxidisers: agents(false),
+            reducers: agents(true),
+            transfers,
+        })
+    }
+}
+
+// Transfers with their multipliers: electrons given = electrons taken
+fn electron_balance(
+    reagents: &[Substance],
+    products: &[Substance],
+) -> Option<Vec<ElectronTransfer>> {
+    let mut transfers = get_transfers(reagents, products)?;
+    let electrons_of = |oxidation: bool| {
+        transfers
+            .iter()
+            .filter(|t| t.is_oxidation() == oxidation)
+            .try_fold(0u8, |sum, t| sum.checked_add(t.electrons()))
+    };
+    let (lost, gained) = (electrons_of(true)?, electrons_of(false)?);
+    if lost == 0 || gained == 0 {
+        return None;
+    }
+
+    let electrons = lcm(lost, gained)?;
+    for t in transfers.iter_mut() {
+        t.multiplier = match t.is_oxidation() {
+            true => electrons / lost,
+            false => electrons / gained,
+        };
+    }
+    transfers.sort_by_key(|t| !t.is_oxidation());
+    Some(transfers)
+}
+
+// Rows for balancing which fix the ratio of the reducer and the oxidiser by the multipliers:
+// atoms in the new states of products are atoms × multiplier of the transfers.
+// Only for one oxidation and one reduction to states which reagents don't have
+pub fn multiplier_rows(reagents: &[Substance], products: &[Substance]) -> Option<Vec<Vec<i64>>> {
+    let transfers = electron_balance(reagents, products)?;
+    let [oxidation, reduction] = transfers.as_slice() else {
+        return None;
+    };
+    let atoms_in = |substances: &[Substance], t: &ElectronTransfer| -> Vec<i64> {
+        substances
+            .iter()
+            .map(|s| {
+                s.blocks()
+                    .get(&t.element)
+                    .filter(|sb| sb.oxidation_state == t.to)
+                    .map_or(0, |sb| sb.index as i64)
+            })
+            .collect()
+    };
+    if [oxidation, reduction]
+        .iter()
+        .any(|t| atoms_in(reagents, t).iter().any(|n| *n > 0))
+    {
+        return None;
+    }
+
+    let per = |t: &ElectronTransfer| (t.atoms * t.multiplier) as i64;
+    let mut row = vec![0; reagents.len()];
+    for (o, r) in atoms_in(products, oxidation)
+        .iter()
+        .zip(atoms_in(products, reduction))
+    {
+        row.push(per(reduction) * o - per(oxidation) * r);
+    }
+    Some(vec![row])
+}
+
+// Electrons given by products minus taken by them is 0,
+// the row for balancing when atoms alone are not enough
+pub fn electron_row(reagents: &[Substance], products: &[Substance]) -> Option<Vec<i64>> {
+    let transfers = get_transfers(reagents, products)?;
+    let mut row = vec![0; reagents.len()];
+    for product in products {
+        let blocks = product.blocks();
+        let electrons: i64 = transfers
+            .iter()
+            .filter_map(|t| {
+                let sb = blocks.get(&t.element)?;
+                (sb.oxidation_state == t.to).then_some(sb.index as i64 * (t.to - t.from) as i64)
+            })
+            .sum();
+        row.push(electrons);
+    }
+    row.iter().any(|e| *e != 0).then_some(row)
+}
+
+// The states which disappear go to the ones which appear: Cl⁻¹ → Cl⁰ for KMnO₄ + HCl
+fn get_transfers(reagents: &[Substance], products: &[Substance]) -> Option<Vec<ElectronTransfer>> {
+    let states = |substances: &[Substance], element: &str| {
+        let mut res: Vec<i8> = substances
+            .iter()
+            .filter_map(|s| s.blocks().get(element).map(|sb| sb.oxidation_state))
+            .collect();
+        res.sort();
+        res.dedup();
+        res
+    };
+
+    let mut elements: Vec<String> = reagents
+        .iter()
+        .flat_map(|s| s.blocks().into_keys())
+        .collect();
+    elements.sort();
+    elements.dedup();
+
+    let mut transfers = Vec::new();
+    for element in elements {
+        let before = states(reagents, &element);
+        let after = states(products, &element);
+        let gone: Vec<i8> = before
+            .iter()
+            .filter(|s| !after.contains(s))
+            .copied()
+            .collect();
+        let new: Vec<i8> = after
+            .iter()
+            .filter(|s| !before.contains(s))
+            .copied()
+            .collect();
+        if gone.is_empty() && new.is_empty() {
+            continue;
+        }
+        let sources = if gone.is_empty() { before } else { gone };
+        let targets = if new.is_empty() { after } else { new };
+
+        // one state gives several or several states give one
+        let pairs: Vec<(i8, i8)> = match (sources.as_slice(), targets.as_slice()) {
+            ([from], targets) => targets.iter().map(|to| (*from, *to)).collect(),
+            (sources, [to]) => sources.iter().map(|from| (*from, *to)).collect(),
+            _ => return None,
+        };
+
+        // simple substances change their state as molecules: O₂⁰ + 4e⁻ → 2O⁻²
+        let atoms = reagents
+            .iter()
+            .chain(products)
+            .filter(|s| s.class == SubstanceClass::Simple)
+            .filter_map(|s| s.blocks().get(&element).map(|sb| sb.index))
+            .max()
+            .unwrap_or(1);
+        for (from, to) in pairs.into_iter().filter(|(from, to)| from != to) {
+            transfers.push(ElectronTransfer {
+                element: element.clone(),
+                from,
+                to,
+                atoms,
+                multiplier: 1,
+            });
+        }
+    }
+
+    (!transfers.is_empty()).then_some(transfers)
+}
+
+// Oxidation state with its sign first: ⁺³, ⁻², ⁰
+fn superscript_state(state: i8) -> String {
+    let sign = match state {
+        0 => "",
+        1.. => "⁺",
+        _ => "⁻",
+    };
+    format!("{}{}", sign, superscript(state.unsigned_abs() as u16))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reaction::conditions::Conditions;
+
+    #[test]
+    fn reaction_redox() {
+        let p_t = PeriodicTable::new();
+        let calculate = |formulas: &[&str]| {
+            let reagents = formulas
+                .iter()
+                .map(|f| Substance::from_string(f, &p_t).unwrap())
+                .collect();
+            Reaction::try_calculate_from(reagents, true).unwrap()
+        };
+
+        let redox = calculate(&["Fe", "O2"]).redox(&p_t).unwrap();
+        assert_eq!(
+            "Fe⁰ − 3e⁻ → Fe⁺³ ×4\nO2⁰ + 4e⁻ → 2O⁻² ×3",
+            redox.to_string()
+        );
+        assert_eq!("O2", redox.oxidisers[0].formula(&p_t));
+        assert_eq!("Fe", redox.reducers[0].formula(&p_t));
+
+        let redox = calculate(&["KMnO4", "HCl"]).redox(&p_t).unwrap();
+        assert_eq!(
+            "2Cl⁻¹ − 2e⁻ → Cl2⁰ ×5\nMn⁺⁷ + 5e⁻ → Mn⁺² ×2",
+            redox.to_string()
+        );
+        assert_eq!("KMnO4", redox.oxidisers[0].formula(&p_t));
+        assert_eq!("HCl", redox.reducers[0].formula(&p_t));
+
+        assert!(calculate(&["NaOH", "HCl"]).redox(&p_t).is_none());
+    }
+
+    #[test]
+    fn reaction_electron_balance() {
+        let p_t = PeriodicTable::new();
+        let substances = |formulas: &[&str]| -> Vec<Substance> {
+            formulas
+                .iter()
+                .map(|f| Substance::from_string(f, &p_t).unwrap())
+                .collect()
+        };
+        // atoms alone allow any ratio of KMnO₄ and H₂O₂, the multipliers O ×5 and Mn ×2 pick it
+        let reaction = Reaction {
+            reagents: substances(&["KMnO4", "H2O2", "H2SO4"]),
+            conditions: Conditions::default(),
+            products: substances(&["MnSO4", "K2SO4", "O2", "H2O"]),
+            rtype: crate::reaction::ReactionType::Exchange,
+            reason: None,
+            reversible: false,
+        };
+        assert!(multiplier_rows(&reaction.reagents, &reaction.products).is_some());
+        let multipliers: Vec<u8> = reaction
+            .redox(&p_t)
+            .unwrap()
+            .transfers
+            .iter()
+            .map(|t| t.multiplier)
+            .collect();
+        assert_eq!(vec![5, 2], multipliers);
+        assert_eq!(
+            Ok((vec![2, 5, 3], vec![2, 1, 5, 8])),
+            reaction.coefficients()
+        );
+
+        // 2KMnO₄ + 16HCl: Mn ×2 and Cl₂ ×5
+        let reaction = Reaction::try_calculate_from(substances(&["KMnO4", "HCl"]), false).unwrap();
+        assert!(multiplier_rows(&reaction.reagents, &reaction.products).is_some());
+        let (r_coefs, p_coefs) = reaction.coefficients().unwrap();
+        let cl2 = reaction
+            .products
+            .iter()
+            .position(|p| p.formula(&p_t) == "Cl2")
+            .unwrap();
+        assert_eq!((2, 5), (r_coefs[0], p_coefs[cl2]));
+    }
+}
@@ -0,0 +1,496 @@
+use std::fs;
+use std::sync::LazyLock;
+
+use super::{
+    conditions::Conditions, get_exchange_ions, get_redox_product, Reaction, ReactionFn,
+    ReactionType,
+};
+use crate::{
+    matter::{
+        solubility::Solubility,
+        substance::{is_composition, Substance, SubstanceClass},
+    },
+    quantity::Pressure,
+    utils::{
+        periodic_table::PeriodicTable,
+        redox_table::{OXIDISERS, REDUCERS},
+        rule_table::DEFAULT_RULES,
+    },
+};
+
+// Rules written in Rust which data rules refer to by name: (name, function, patterns
+// a rule has to have for it, the most reagents it takes). Rules without them are rejected,
+// so the functions may rely on their reagents
+#[rustfmt::skip]
+const BUILTIN_RULES: [(&str, ReactionFn, &str, usize); 15] = [
+    ("decomposition", Reaction::reaction_decomposition, "Any", 1),
+    ("nonme_nonme", Reaction::reaction_nonme_nonme, "Simple + Simple", 2),
+    ("me_antime", Reaction::reaction_me_antime, "Simple:metal + Simple:nonmetal", 2),
+    ("me_water", Reaction::reaction_me_water, "Simple:metal + Oxide:water", 2),
+    ("water", Reaction::reaction_water, "Oxide:water + Any", 2),
+    ("me_oxidising_acid", Reaction::reaction_me_oxidising_acid, "Simple:metal + Acid:oxidising", 2),
+    ("me_acid", Reaction::reaction_me_acid, "Simple:metal + Acid", 2),
+    ("me_salt", Reaction::reaction_me_salt, "Simple:metal + Salt", 2),
+    ("acid_base", Reaction::reaction_acid_base, "Acid + Base", 2),
+    ("redox_acidic", Reaction::reaction_redox_acidic, "Salt:oxidiser + Acid", 3),
+    ("exchange", Reaction::reaction_exchange, "Any + Any", 2),
+    ("oxide_acid", Reaction::reaction_oxide_acid, "Oxide + Acid", 2),
+    ("oxide_base", Reaction::reaction_oxide_base, "Oxide + Base", 3),
+    ("oxide_oxide", Reaction::reaction_oxide_oxide, "Oxide + Oxide", 2),
+    ("simple_base_water", Reaction::reaction_simple_base_water, "Simple + Base + Oxide:water", 3),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Property {
+    // simple substances: H₂ counts as a metal, but not as a non-metal
+    Metal,
+    NonMetal,
+    Hydrogen,
+    Water,
+    // HNO₃ and concentrated H₂SO₄
+    Oxidising,
+    // it has an ion of the redox table
+    Oxidiser,
+    Reducer,
+    Concentrated,
+    Soluble,
+    Element(String),
+    Group(u8),
+    Formula(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    // None for any class
+    pub class: Option<SubstanceClass>,
+    pub properties: Vec<Property>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Requirement {
+    Heating,
+    Light,
+    Melt,
+    Catalyst(String),
+    // at least this pressure: N₂ + 3H₂ under 20 MPa
+    Pressure(Pressure),
+    Solvent(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Action {
+    Builtin(&'static str, ReactionFn),
+    Products(Vec<String>, ReactionType),
+}
+
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub priority: i32,
+    pub reagents: Vec<Pattern>,
+    pub requirements: Vec<Requirement>,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone)]
+pub struct RuleSet {
+    pub rules: Vec<Rule>,
+}
+
+// DEFAULT_RULES are parsed once
+static DEFAULT_RULE_SET: LazyLock<RuleSet> =
+    LazyLock::new(|| RuleSet::parse(DEFAULT_RULES).unwrap());
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::defaults().clone()
+    }
+}
+
+impl RuleSet {
+    pub fn defaults() -> &'static Self {
+        &DEFAULT_RULE_SET
+    }
+
+    // The format is described in utils/rule_table.rs, # starts a comment
+    pub fn parse(text: &str) -> Result<Self, &'static str> {
+        let p_t = PeriodicTable::new();
+        let mut rules = Vec::new();
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            rules.push(Rule::parse(line, &p_t)?);
+        }
+        Ok(Self { rules })
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, &'static str> {
+        let text = fs::read_to_string(path).map_err(|_| "Can't read the file of rules")?;
+        Self::parse(&text)
+    }
+
+    // Rules of the other set go after these ones
+    pub fn extend(&mut self, other: RuleSet) {
+        self.rules.extend(other.rules);
+    }
+
+    pub fn find(
+        &self,
+        reagents: &[Substance],
+        conditions: &Conditions,
+        p_t: &PeriodicTable,
+    ) -> Option<&Rule> {
+        let mut res: Option<&Rule> = None;
+        for rule in &self.rules {
+            if !rule.matches(reagents, conditions, p_t) {
+                continue;
+            }
+            let better = res.is_none_or(|best| {
+                (rule.priority, rule.specificity()) > (best.priority, best.specificity())
+            });
+            if better {
+                res = Some(rule);
+            }
+        }
+        res
+    }
+}
+
+impl Rule {
+    fn parse(line: &str, p_t: &PeriodicTable) -> Result<Self, &'static str> {
+        let columns: Vec<&str> = line.split('|').map(str::trim).collect();
+        if columns.len() < 4 || columns.len() > 5 {
+            return Err("Rule needs priority, reagents, conditions and action");
+        }
+
+        let priority = columns[0]
+            .parse()
+            .map_err(|_| "Priority of rule has to be a number")?;
+        let mut reagents = Vec::new();
+        for pattern in columns[1].split('+') {
+            reagents.push(Pattern::parse(pattern.trim())?);
+        }
+        let mut requirements = Vec::new();
+        for requirement in columns[2].split(',').map(str::trim) {
+            requirements.push(match requirement.split_once('=') {
+                _ if requirement.is_empty() => continue,
+                Some(("catalyst", catalyst)) => Requirement::Catalyst(catalyst.to_string()),
+                Some(("pressure", kpa)) => Requirement::Pressure(Pressure::from_kilopascals(
+                    kpa.parse()
+                        .map_err(|_| "Pressure of rule has to be a number of kPa")?,
+                )),
+                Some(("solvent", solvent)) => Requirement::Solvent(solvent.to_string()),
+                None if requirement == "heating" => Requirement::Heating,
+                None if requirement == "light" => Requirement::Light,
+                None if requirement == "melt" => Requirement::Melt,
+                _ => return Err("Unknown condition of rule"),
+            });
+        }
+
+        let action = match BUILTIN_RULES.iter().find(|(name, ..)| *name == columns[3]) {
+            Some((name, rule, needed, most)) => {
+                let mut needed_patterns = Vec::new();
+                for pattern in needed.split('+') {
+                    needed_patterns.push(Pattern::parse(pattern.trim())?);
+                }
+                let mut used = vec![false; reagents.len()];
+                if reagents.len() > *most || !cover(&needed_patterns, &reagents, &mut used) {
+                    return Err("Reagents of rule don't fit its built-in action");
+                }
+                Action::Builtin(name, *rule)
+            }
+            None => {
+                let mut products = Vec::new();
+                for product in columns[3].split('+').map(str::trim) {
+                    Substance::from_string(product, p_t).map_err(|_| "Unknown product of rule")?;
+                    products.push(product.to_string());
+                }
+                let rtype = match columns.get(4).copied().unwrap_or("Combination") {
+                    "Combination" => ReactionType::Combination,
+                    "Decomposition" => ReactionType::Decomposition,
+                    "Exchange" => ReactionType::Exchange,
+                    "Substition" => ReactionType::Substition,
+                    _ => return Err("Unknown type of reaction"),
+                };
+                Action::Products(products, rtype)
+            }
+        };
+
+        Ok(Self {
+            priority,
+            reagents,
+            requirements,
+            action,
+        })
+    }
+
+    pub fn apply(
+        &self,
+        reagents: &Vec<Substance>,
+        amounts: Option<&[u8]>,
+        conditions: &Conditions,
+        p_t: &PeriodicTable,
+    ) -> Result<(Vec<Substance>, ReactionType, Option<&'static str>), &'static str> {
+        match &self.action {
+            Action::Builtin(_, rule) => rule(reagents, amounts, conditions, p_t),
+            Action::Products(products, rtype) => {
+                let mut res = Vec::new();
+                for product in products {
+                    res.push(Substance::from_string(product, p_t)?);
+                }
+                Ok((res, *rtype, None))
+            }
+        }
+    }
+
+    fn matches(
+        &self,
+        reagents: &[Substance],
+        conditions: &Conditions,
+        p_t: &PeriodicTable,
+    ) -> bool {
+        let fulfilled = self.requirements.iter().all(|r| match r {
+            Requirement::Heating => conditions.is_heated(),
+            Requirement::Light => conditions.light,
+            Requirement::Melt => conditions.melt,
+            Requirement::Catalyst(c) => conditions.catalyst.as_deref() == Some(c.as_str()),
+            Requirement::Pressure(p) => conditions.pressure >= *p,
+            Requirement::Solvent(s) => conditions.solvent == *s,
+        });
+        let mut used = vec![false; reagents.len()];
+        fulfilled
+            && self.reagents.len() == reagents.len()
+            && assign(&self.reagents, reagents, &mut used, p_t)
+    }
+
+    // The more conditions the rule has, the more specific it is
+    fn specificity(&self) -> usize {
+        let patterns: usize = self
+            .reagents
+            .iter()
+            .map(|p| p.class.is_some() as usize + p.properties.len())
+            .sum();
+        patterns + self.requirements.len()
+    }
+}
+
+impl Pattern {
+    fn parse(s: &str) -> Result<Self, &'static str> {
+        let mut parts = s.split(':').map(str::trim);
+        let class = match parts.next().unwrap_or("") {
+            "Any" => None,
+            "Simple" => Some(SubstanceClass::Simple),
+            "Hydride" => Some(SubstanceClass::Hydride),
+            "Oxide" => Some(SubstanceClass::Oxide),
+            "Peroxide" => Some(SubstanceClass::Peroxide),
+            "Base" => Some(SubstanceClass::Base),
+            "Acid" => Some(SubstanceClass::Acid),
+            "Salt" => Some(SubstanceClass::Salt),
+            "Binary" => Some(SubstanceClass::Binary),
+            _ => return Err("Unknown class of reagent in rule"),
+        };
+
+        let mut properties = Vec::new();
+        for part in parts {
+            properties.push(match part.split_once('=') {
+                Some(("element", name)) => Property::Element(name.to_string()),
+                Some(("formula", formula)) => Property::Formula(formula.to_string()),
+                Some(("group", group)) => {
+                    Property::Group(group.parse().map_err(|_| "Group has to be a number")?)
+                }
+                None if part == "metal" => Property::Metal,
+                None if part == "nonmetal" => Property::NonMetal,
+                None if part == "hydrogen" => Property::Hydrogen,
+                None if part == "water" => Property::Water,
+                None if part == "oxidising" => Property::Oxidising,
+                None if part == "oxidiser" => Property::Oxidiser,
+                None if part == "reducer" => Property::Reducer,
+                None if part == "concentrated" => Property::Concentrated,
+                None if part == "soluble" => Property::Soluble,
+                _ => return Err("Unknown property of reagent in rule"),
+            });
+        }
+
+        Ok(Self { class, properties })
+    }
+
+    // Every substance which the other pattern matches is matched by this one too
+    fn covers(&self, other: &Pattern) -> bool {
+        self.class.is_none_or(|class| other.class == Some(class))
+            && self.properties.iter().all(|p| other.properties.contains(p))
+    }
+
+    fn matches(&self, substance: &Substance, p_t: &PeriodicTable) -> bool {
+        if self.class.is_some_and(|class| class != substance.class) {
+            return false;
+        }
+        self.properties
+            .iter()
+            .all(|property| has_property(substance, property, p_t))
+    }
+}
+
+fn has_property(substance: &Substance, property: &Property, p_t: &PeriodicTable) -> bool {
+    let (me, anti_me) = (substance.me.len(), substance.anti_me.len());
+    let redox_ions = |table: &[(&str, i8, &str, i8)]| {
+        get_exchange_ions(substance, p_t).is_ok_and(|(cation, anion)| {
+            get_redox_product(table, &anion, p_t).is_some()
+                || get_redox_product(table, &cation, p_t).is_some()
+        })
+    };
+    match property {
+        Property::Metal => (me, anti_me) == (1, 0),
+        Property::NonMetal => (me, anti_me) == (0, 1),
+        Property::Hydrogen => {
+            substance.class == SubstanceClass::Simple && substance.me.contains_key("H")
+        }
+        Property::Water => is_composition(&substance.blocks(), "H2O", p_t),
+        Property::Oxidising => {
+            let formula = substance.formula(p_t);
+            formula == "HNO3" || formula == "H2SO4" && substance.is_concentrated(p_t)
+        }
+        Property::Oxidiser => redox_ions(&OXIDISERS),
+        Property::Reducer => match substance.class {
+            // H⁺ of acids isn't a reducer, only the residue may be
+            SubstanceClass::Acid => substance
+                .acid_properties(p_t)
+                .is_some_and(|acid| get_redox_product(&REDUCERS, &acid.residue, p_t).is_some()),
+            _ => redox_ions(&REDUCERS),
+        },
+        Property::Concentrated => substance.is_concentrated(p_t),
+        Property::Soluble => substance.solubility(p_t) == Some(Solubility::Soluble),
+        Property::Element(name) => substance.blocks().contains_key(name),
+        Property::Group(group) => substance
+            .blocks()
+            .values()
+            .any(|sb| sb.element.group == *group),
+        Property::Formula(formula) => is_composition(&substance.blocks(), formula, p_t),
+    }
+}
+
+// Every pattern gets its own reagent
+fn assign(
+    patterns: &[Pattern],
+    reagents: &[Substance],
+    used: &mut [bool],
+    p_t: &PeriodicTable,
+) -> bool {
+    let Some((pattern, rest)) = patterns.split_first() else {
+        return true;
+    };
+    for (i, reagent) in reagents.iter().enumerate() {
+        if used[i] || !pattern.matches(reagent, p_t) {
+            continue;
+        }
+        used[i] = true;
+        if assign(rest, reagents, used, p_t) {
+            return true;
+        }
+        used[i] = false;
+    }
+    false
+}
+
+// Every needed pattern gets its own pattern of the rule
+fn cover(needed: &[Pattern], patterns: &[Pattern], used: &mut [bool]) -> bool {
+    let Some((pattern, rest)) = needed.split_first() else {
+        return true;
+    };
+    for (i, other) in patterns.iter().enumerate() {
+        if used[i] || !pattern.covers(other) {
+            continue;
+        }
+        used[i] = true;
+        if cover(rest, patterns, used) {
+            return true;
+        }
+        used[i] = false;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rule_set() {
+        let p_t = PeriodicTable::new();
+        let rules = RuleSet::default();
+        let reagents = |formulas: &[&str]| -> Vec<Substance> {
+            formulas
+                .iter()
+                .map(|f| Substance::from_string(f, &p_t).unwrap())
+                .collect()
+        };
+        let rule_name = |formulas: &[&str], rules: &RuleSet| match rules.find(
+            &reagents(formulas),
+            &Conditions::default(),
+            &p_t,
+        ) {
+            Some(Rule {
+                action: Action::Builtin(name, _),
+                ..
+            }) => Some(*name),
+            _ => None,
+        };
+
+        assert_eq!(Some("nonme_nonme"), rule_name(&["H2", "Cl2"], &rules));
+        assert_eq!(Some("me_antime"), rule_name(&["Na", "Cl2"], &rules));
+        assert_eq!(Some("water"), rule_name(&["H2O", "SO3"], &rules));
+        assert_eq!(Some("redox_acidic"), rule_name(&["HCl", "KMnO4"], &rules));
+        assert_eq!(Some("exchange"), rule_name(&["NaCl", "AgNO3"], &rules));
+        assert_eq!(None, rule_name(&["Na", "Cl2", "O2"], &rules));
+
+        let liquid_ammonia = RuleSet::parse(
+            "200 | Simple:metal + Simple:nonmetal | solvent=NH3, pressure=500 | me_antime",
+        )
+        .unwrap();
+        assert_eq!(None, rule_name(&["Na", "S"], &liquid_ammonia));
+        let conditions = Conditions {
+            pressure: Pressure::from_kilopascals(800.0),
+            solvent: "NH3".to_string(),
+            ..Default::default()
+        };
+        assert!(liquid_ammonia
+            .find(&reagents(&["Na", "S"]), &conditions, &p_t)
+            .is_some());
+
+        assert!(RuleSet::parse("10 | Metal + Acid | | me_acid").is_err());
+        // built-in rules get only the reagents they work with
+        assert!(RuleSet::parse("10 | Simple + Acid | | me_acid").is_err());
+        assert!(RuleSet::parse("10 | Salt | | exchange").is_err());
+        assert!(RuleSet::parse("10 | Oxide + Oxide + Oxide | | oxide_oxide").is_err());
+        assert!(RuleSet::parse("10 | Simple + Acid | pressure=high | me_acid").is_err());
+        assert!(RuleSet::parse("ten | Simple + Acid | | me_acid").is_err());
+        assert!(RuleSet::parse("10 | Simple + Acid | | Xx + O2").is_err());
+    }
+
+    #[test]
+    fn rule_set_from_file() {
+        let p_t = PeriodicTable::new();
+        let path =
+            std::env::temp_dir().join(format!("al_chemistry_rules_{}.txt", std::process::id()));
+        fs::write(
+            &path,
+            "# Fe³⁺ oxidises copper\n\
+             200 | Simple:element=Cu + Salt:formula=FeCl3 | | CuCl2 + FeCl2 | Exchange\n",
+        )
+        .unwrap();
+        let mut rules = RuleSet::default();
+        let from_file = RuleSet::from_file(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        rules.extend(from_file.unwrap());
+
+        let reagents = vec![
+            Substance::from_string("Cu", &p_t).unwrap(),
+            Substance::from_string("FeCl3", &p_t).unwrap(),
+        ];
+        let reaction =
+            Reaction::try_calculate_with_rules(reagents, Conditions::default(), &rules).unwrap();
+        assert_eq!("Cu + 2FeCl3 → CuCl2 + 2FeCl2", reaction.to_string());
+
+        assert!(RuleSet::from_file("/there/is/no/such/file").is_err());
+    }
+}
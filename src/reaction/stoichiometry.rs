@@ -0,0 +1,219 @@
+use super::Reaction;
+use crate::{
+    matter::{gas::GasConditions, substance::Substance},
+    quantity::{Amount, Mass, MolarMass, Molarity, Volume},
+    utils::periodic_table::PeriodicTable,
+};
+
+// What is known about a reagent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quantity {
+    Mass(Mass),
+    Amount(Amount),
+    GasVolume {
+        volume: Volume,
+        conditions: GasConditions,
+    },
+    Solution {
+        volume: Volume,
+        concentration: Molarity,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Portion {
+    pub amount: Amount,
+    pub mass: Mass,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stoichiometry {
+    // index of the reagent which is used up first
+    pub limiting: usize,
+    // every participant takes coefficient × extent
+    pub extent: Amount,
+    pub reacted: Vec<Portion>,
+    // what is left of the reagents in excess, 0 for the ones not given
+    pub left: Vec<Portion>,
+    // theoretical yield
+    pub products: Vec<Portion>,
+}
+
+impl Quantity {
+    pub fn amount(&self, molar_mass: MolarMass) -> Amount {
+        match *self {
+            Quantity::Mass(mass) => mass / molar_mass,
+            Quantity::Amount(amount) => amount,
+            Quantity::GasVolume { volume, conditions } => conditions.amount(volume),
+            Quantity::Solution {
+                volume,
+                concentration,
+            } => volume * concentration,
+        }
+    }
+}
+
+impl Portion {
+    fn new(amount: Amount, molar_mass: MolarMass) -> Self {
+        Self {
+            amount,
+            mass: amount * molar_mass,
+        }
+    }
+
+    // if it's a gas
+    pub fn gas_volume(&self, conditions: &GasConditions) -> Volume {
+        conditions.volume(self.amount)
+    }
+}
+
+impl Stoichiometry {
+    // %, actual mass of the product against its theoretical yield
+    pub fn percent_yield(&self, product: usize, actual: Mass) -> Result<f64, &'static str> {
+        let theoretical = self
+            .products
+            .get(product)
+            .ok_or("There is no such product")?;
+        if theoretical.mass.grams() <= 0.0 {
+            return Err("Theoretical yield is zero");
+        }
+        Ok(actual / theoretical.mass * 100.0)
+    }
+}
+
+impl Reaction {
+    // Quantities are given for some reagents, the others are considered enough
+    pub fn stoichiometry(
+        &self,
+        quantities: &[Option<Quantity>],
+    ) -> Result<Stoichiometry, &'static str> {
+        if quantities.len() != self.reagents.len() {
+            return Err("Quantity or None is required for every reagent");
+        }
+        let (r_coefs, p_coefs) = self.coefficients()?;
+
+        let mut limiting: Option<(usize, Amount)> = None;
+        for (i, quantity) in quantities.iter().enumerate() {
+            let Some(quantity) = quantity else {
+                continue;
+            };
+            let amount = quantity.amount(self.reagents[i].molar_mass());
+            if !amount.moles().is_finite() || amount.moles() <= 0.0 {
+                return Err("Quantity of the reagent has to be positive");
+            }
+            let extent = amount / r_coefs[i] as f64;
+            if limiting.is_none_or(|(_, min)| extent < min) {
+                limiting = Some((i, extent));
+            }
+        }
+        let (limiting, extent) = limiting.ok_or("Quantity of at least one reagent is required")?;
+
+        let mut reacted = Vec::new();
+        let mut left = Vec::new();
+        for ((reagent, k), quantity) in self.reagents.iter().zip(&r_coefs).zip(quantities) {
+            let molar_mass = reagent.molar_mass();
+            let amount = extent * *k as f64;
+            let given = quantity.map_or(amount, |q| q.amount(molar_mass));
+            reacted.push(Portion::new(amount, molar_mass));
+            left.push(Portion::new(given - amount, molar_mass));
+        }
+        let products = self
+            .products
+            .iter()
+            .zip(&p_coefs)
+            .map(|(product, k)| Portion::new(extent * *k as f64, product.molar_mass()))
+            .collect();
+
+        Ok(Stoichiometry {
+            limiting,
+            extent,
+            reacted,
+            left,
+            products,
+        })
+    }
+
+    // Volumes of the gases which leave the reaction: H₂ of Zn + 2HCl → ZnCl₂ + H₂↑
+    pub fn evolved_gases(
+        &self,
+        quantities: &[Option<Quantity>],
+        conditions: &GasConditions,
+        p_t: &PeriodicTable,
+    ) -> Result<Vec<(Substance, Volume)>, &'static str> {
+        let stoichiometry = self.stoichiometry(quantities)?;
+        Ok(self
+            .products
+            .iter()
+            .zip(&stoichiometry.products)
+            .filter(|(product, _)| self.marker(product, p_t) == "↑")
+            .map(|(product, portion)| (product.clone(), conditions.volume(portion.amount)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matter::gas::{NTP, STP};
+
+    fn reaction(formulas: &[&str], p_t: &PeriodicTable) -> Reaction {
+        let reagents = formulas
+            .iter()
+            .map(|f| Substance::from_string(f, p_t).unwrap())
+            .collect();
+        Reaction::try_calculate_from(reagents, true).unwrap()
+    }
+
+    #[test]
+    fn stoichiometry() {
+        let p_t = PeriodicTable::new();
+        let close = |a: f64, b: f64| (a - b).abs() < 0.01;
+
+        // 2H₂ + O₂ → 2H₂O: 2 mol of H₂ need only 1 mol of O₂
+        let water = reaction(&["H2", "O2"], &p_t);
+        let s = water
+            .stoichiometry(&[
+                Some(Quantity::Mass("4.0316 g".parse().unwrap())),
+                Some(Quantity::Amount(Amount::from_moles(1.5))),
+            ])
+            .unwrap();
+        assert_eq!(0, s.limiting);
+        assert!(close(1.0, s.reacted[1].amount.moles()));
+        assert!(close(0.5, s.left[1].amount.moles()));
+        assert!(close(16.0, s.left[1].mass.grams()));
+        assert!(close(36.03, s.products[0].mass.grams()));
+        let actual = Mass::from_grams(27.02);
+        assert!(close(75.0, s.percent_yield(0, actual).unwrap()));
+
+        // Zn + 2HCl → ZnCl₂ + H₂↑: 100 mL of 1 M acid for 13 g of zinc
+        let zinc = reaction(&["Zn", "HCl"], &p_t);
+        let s = zinc
+            .stoichiometry(&[
+                Some(Quantity::Mass(Mass::from_grams(13.0))),
+                Some(Quantity::Solution {
+                    volume: "100 mL".parse().unwrap(),
+                    concentration: "1 M".parse().unwrap(),
+                }),
+            ])
+            .unwrap();
+        assert_eq!(1, s.limiting);
+        assert!(close(0.05, s.products[1].amount.moles()));
+        assert!(close(1.12, s.products[1].gas_volume(&STP).litres()));
+
+        let gas = Quantity::GasVolume {
+            volume: Volume::from_litres(2.24),
+            conditions: STP,
+        };
+        let s = zinc.stoichiometry(&[None, Some(gas)]).unwrap();
+        assert!(close(3.27, s.reacted[0].mass.grams()));
+        assert!(zinc.stoichiometry(&[None, None]).is_err());
+
+        let zinc_mass = Quantity::Mass(Mass::from_grams(6.5));
+        let gases = zinc
+            .evolved_gases(&[Some(zinc_mass), None], &NTP, &p_t)
+            .unwrap();
+        assert_eq!(1, gases.len());
+        assert_eq!("H2", gases[0].0.formula(&p_t));
+        assert!(close(2.39, gases[0].1.litres()));
+    }
+}
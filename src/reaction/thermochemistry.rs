@@ -0,0 +1,102 @@
+use super::Reaction;
+use crate::{
+    matter::{substance::Substance, thermodynamics::Thermodynamics},
+    quantity::{Energy, Entropy, Mass},
+    utils::periodic_table::PeriodicTable,
+};
+
+impl Reaction {
+    // Hess's law: products minus reagents, every one taken by its coefficient
+    pub fn thermodynamics(&self, p_t: &PeriodicTable) -> Result<Thermodynamics, &'static str> {
+        let (r_coefs, p_coefs) = self.coefficients()?;
+        let mut res = Thermodynamics {
+            enthalpy: Energy::default(),
+            entropy: Entropy::default(),
+            gibbs: Energy::default(),
+        };
+        let sides: [(&[Substance], &[u8], f64); 2] = [
+            (&self.reagents, &r_coefs, -1.0),
+            (&self.products, &p_coefs, 1.0),
+        ];
+        for (substances, coefs, sign) in sides {
+            for (s, k) in substances.iter().zip(coefs) {
+                let data = s
+                    .thermodynamics(p_t)
+                    .ok_or("Thermodynamic data of a substance is unknown")?;
+                let k = sign * *k as f64;
+                res.enthalpy = res.enthalpy + data.enthalpy * k;
+                res.entropy = res.entropy + data.entropy * k;
+                res.gibbs = res.gibbs + data.gibbs * k;
+            }
+        }
+        Ok(res)
+    }
+
+    // Heat which the given mass of a reagent releases, negative if the heat is absorbed
+    pub fn heat(
+        &self,
+        reagent: usize,
+        mass: Mass,
+        p_t: &PeriodicTable,
+    ) -> Result<Energy, &'static str> {
+        let substance = self
+            .reagents
+            .get(reagent)
+            .ok_or("There is no such reagent")?;
+        let (r_coefs, _) = self.coefficients()?;
+        let reactions = (mass / substance.molar_mass()).moles() / r_coefs[reagent] as f64;
+        Ok(self.thermodynamics(p_t)?.enthalpy * -reactions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quantity::Temperature;
+
+    #[test]
+    fn reaction_thermochemistry() {
+        let p_t = PeriodicTable::new();
+        let close = |a: f64, b: f64| (a - b).abs() < 0.1;
+        let reaction = |formulas: &[&str]| {
+            let reagents = formulas
+                .iter()
+                .map(|f| Substance::from_string(f, &p_t).unwrap())
+                .collect();
+            Reaction::try_calculate_from(reagents, true).unwrap()
+        };
+        let room = Temperature::from_celsius(25.0);
+
+        // 2H₂ + O₂ → 2H₂O
+        let water = reaction(&["H2", "O2"]);
+        let data = water.thermodynamics(&p_t).unwrap();
+        assert!(close(-571.6, data.enthalpy.kilojoules()));
+        assert!(data.is_exothermic());
+        assert!(data.is_spontaneous(room));
+        let t = data.crossover_temperature().unwrap();
+        assert!((t.kelvin() - 1750.2).abs() < 1.0);
+        let heat = water.heat(0, Mass::from_grams(4.0316), &p_t).unwrap();
+        assert!(close(571.6, heat.kilojoules()));
+
+        // CaCO₃ → CaO + CO₂ goes only when heated above ~845°C
+        let limestone = reaction(&["CaCO3"]);
+        let data = limestone.thermodynamics(&p_t).unwrap();
+        assert!(close(179.2, data.enthalpy.kilojoules()));
+        assert!(close(160.2, data.entropy.joules_per_kelvin()));
+        assert!(close(131.4, data.gibbs.kilojoules()));
+        assert!(!data.is_exothermic());
+        assert!(!data.is_spontaneous(room));
+        assert!(data.is_spontaneous(Temperature::from_celsius(900.0)));
+        let t = data.crossover_temperature().unwrap();
+        assert!((t.kelvin() - 1118.6).abs() < 1.0);
+        assert!(
+            limestone
+                .heat(0, Mass::from_grams(100.09), &p_t)
+                .unwrap()
+                .kilojoules()
+                < 0.0
+        );
+
+        assert!(reaction(&["KMnO4"]).thermodynamics(&p_t).is_err());
+    }
+}
@@ -1,3 +1,15 @@
+pub mod acid_table;
+pub mod base_table;
+pub mod decomposition_table;
+pub mod format_util;
+pub mod indicator_table;
+pub mod ion_table;
 pub mod math_util;
 pub mod parser;
 pub mod periodic_table;
+pub mod potential_table;
+pub mod redox_table;
+pub mod rule_table;
+pub mod solubility_table;
+pub mod thermo_table;
+pub mod unit_table;
@@ -0,0 +1,32 @@
+// Common acids: (formula, basicity, pKa of every dissociation step at 25°C).
+// Basicity is the number of H which can be replaced by Me,
+// it's not always the number of H in the formula (H₃PO₃, CH₃COOH)
+#[rustfmt::skip]
+pub const ACIDS: [(&str, u8, &[f64]); 25] = [
+    ("HF", 1, &[3.17]),
+    ("HCl", 1, &[-7.0]),
+    ("HBr", 1, &[-9.0]),
+    ("HI", 1, &[-10.0]),
+    ("H2S", 2, &[7.0, 12.9]),
+    ("HCN", 1, &[9.21]),
+
+    ("HNO3", 1, &[-1.4]),
+    ("HNO2", 1, &[3.35]),
+    ("H2SO4", 2, &[-3.0, 1.99]),
+    ("H2SO3", 2, &[1.85, 7.2]),
+    ("H2CO3", 2, &[6.35, 10.33]),
+    ("H3PO4", 3, &[2.15, 7.2, 12.35]),
+    ("H3PO3", 2, &[1.3, 6.7]),
+    ("H3PO2", 1, &[1.2]),
+    ("H2SiO3", 2, &[9.8, 12.0]),
+    ("H3BO3", 1, &[9.24]),
+    ("CH3COOH", 1, &[4.76]),
+    ("HClO", 1, &[7.53]),
+    ("HClO2", 1, &[1.97]),
+    ("HClO3", 1, &[-1.0]),
+    ("HClO4", 1, &[-10.0]),
+    ("HBrO3", 1, &[-2.0]),
+    ("HIO3", 1, &[0.77]),
+    ("HMnO4", 1, &[-2.25]),
+    ("H2CrO4", 2, &[-0.98, 6.5]),
+];
@@ -0,0 +1,10 @@
+// Weak bases by their cations: (cation, charge, pKb at 25°C of the step which gives the cation:
+// NH₃·H₂O ⇌ NH₄⁺ + OH⁻, Al(OH)²⁺ ⇌ Al³⁺ + OH⁻). The cation hydrolyses with pKa = 14 - pKb.
+// Alkali and alkaline earth metals give strong bases and aren't here
+#[rustfmt::skip]
+pub const WEAK_BASES: [(&str, i8, f64); 12] = [
+    ("NH4", 1, 4.75),
+    ("Mg", 2, 2.6), ("Mn", 2, 3.4), ("Ni", 2, 4.1), ("Co", 2, 4.3),
+    ("Fe", 2, 4.5), ("Zn", 2, 5.0), ("Pb", 2, 6.3), ("Cu", 2, 6.5),
+    ("Al", 3, 9.0), ("Cr", 3, 10.0), ("Fe", 3, 11.8),
+];
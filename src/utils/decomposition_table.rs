@@ -0,0 +1,13 @@
+// Substances which decompose in their own way when heated:
+// (formula, the lowest temperature of decomposition in °C, products)
+#[rustfmt::skip]
+pub const DECOMPOSITIONS: [(&str, f64, &[&str]); 8] = [
+    ("KMnO4", 200.0, &["K2MnO4", "MnO2", "O2"]),
+    ("KClO3", 400.0, &["KCl", "O2"]),
+    ("H2O2", 150.0, &["H2O", "O2"]),
+    ("NH4NO3", 210.0, &["N2O", "H2O"]),
+    ("NH4NO2", 60.0, &["N2", "H2O"]),
+    ("(NH4)2Cr2O7", 180.0, &["Cr2O3", "N2", "H2O"]),
+    ("(NH4)2SO4", 235.0, &["NH3", "NH4HSO4"]),
+    ("H2SiO3", 150.0, &["SiO2", "H2O"]),
+];
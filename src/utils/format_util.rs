@@ -0,0 +1,9 @@
+// Digits of the number as superscripts: 12 - ¹²
+pub fn superscript(n: u16) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+    n.to_string()
+        .chars()
+        .map(|c| DIGITS[c as usize - '0' as usize])
+        .collect()
+}
@@ -0,0 +1,10 @@
+// Acid-base indicators: (name, pH range where the colour changes, colour in acid, colour in base)
+#[rustfmt::skip]
+pub const INDICATORS: [(&str, (f64, f64), &str, &str); 6] = [
+    ("methyl orange", (3.1, 4.4), "red", "yellow"),
+    ("bromocresol green", (3.8, 5.4), "yellow", "blue"),
+    ("methyl red", (4.4, 6.2), "red", "yellow"),
+    ("bromothymol blue", (6.0, 7.6), "yellow", "blue"),
+    ("phenolphthalein", (8.2, 10.0), "colourless", "pink"),
+    ("thymolphthalein", (9.3, 10.5), "colourless", "blue"),
+];
@@ -0,0 +1,45 @@
+// Ions which have their own names: (formula, charge, name).
+// Formula is written the way it's printed - composition is parsed from it.
+// Simple metal cations aren't here: they are named by the element itself
+#[rustfmt::skip]
+pub const IONS: [(&str, i8, &str); 37] = [
+    ("H", 1, "hydrogen"),
+    ("NH4", 1, "ammonium"),
+
+    ("OH", -1, "hydroxide"),
+    ("F", -1, "fluoride"),
+    ("Cl", -1, "chloride"),
+    ("Br", -1, "bromide"),
+    ("I", -1, "iodide"),
+    ("S", -2, "sulfide"),
+    ("HS", -1, "hydrosulfide"),
+    ("CN", -1, "cyanide"),
+
+    ("NO3", -1, "nitrate"),
+    ("NO2", -1, "nitrite"),
+    ("SO4", -2, "sulfate"),
+    ("HSO4", -1, "hydrogen sulfate"),
+    ("SO3", -2, "sulfite"),
+    ("HSO3", -1, "hydrogen sulfite"),
+    ("CO3", -2, "carbonate"),
+    ("HCO3", -1, "hydrogen carbonate"),
+    ("PO4", -3, "phosphate"),
+    ("HPO4", -2, "hydrogen phosphate"),
+    ("H2PO4", -1, "dihydrogen phosphate"),
+    ("HPO3", -2, "phosphite"),
+    ("H2PO3", -1, "hydrogen phosphite"),
+    ("H2PO2", -1, "hypophosphite"),
+    ("SiO3", -2, "silicate"),
+    ("CH3COO", -1, "acetate"),
+    ("ClO", -1, "hypochlorite"),
+    ("ClO2", -1, "chlorite"),
+    ("ClO3", -1, "chlorate"),
+    ("ClO4", -1, "perchlorate"),
+    ("BrO3", -1, "bromate"),
+    ("IO3", -1, "iodate"),
+    ("MnO4", -1, "permanganate"),
+    ("MnO4", -2, "manganate"),
+    ("CrO4", -2, "chromate"),
+    ("Cr2O7", -2, "dichromate"),
+    ("AlO2", -1, "aluminate"),
+];
@@ -10,7 +10,65 @@ pub fn gcd(mut a: u8, mut b: u8) -> u8 {
     }
 }
 
+// None if it doesn't fit in u8
 #[inline]
-pub fn lcm(a: u8, b: u8) -> u8 {
-    a * b / gcd(a, b)
+pub fn lcm(a: u8, b: u8) -> Option<u8> {
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+// The only (up to a factor) non-zero integer x with matrix * x = 0,
+// None if there is no such x or there are several independent ones
+pub fn null_vector(matrix: &[Vec<i64>]) -> Option<Vec<i64>> {
+    let n = matrix.first()?.len();
+    let mut m: Vec<Vec<i64>> = matrix.to_vec();
+    let mut pivots: Vec<usize> = Vec::new();
+
+    // integer Gauss-Jordan elimination
+    for col in 0..n {
+        let row = pivots.len();
+        let Some(p) = (row..m.len()).find(|&r| m[r][col] != 0) else {
+            continue;
+        };
+        m.swap(row, p);
+        let pivot_row = m[row].clone();
+        for (r, other) in m.iter_mut().enumerate() {
+            if r == row || other[col] == 0 {
+                continue;
+            }
+            let (a, b) = (pivot_row[col], other[col]);
+            for (v, p) in other.iter_mut().zip(&pivot_row) {
+                *v = *v * a - p * b;
+            }
+            let g = other.iter().fold(0, |g, &v| gcd_i64(g, v));
+            if g > 1 {
+                other.iter_mut().for_each(|v| *v /= g);
+            }
+        }
+        pivots.push(col);
+    }
+
+    if n - pivots.len() != 1 {
+        return None;
+    }
+    let free = (0..n).find(|c| !pivots.contains(c))?;
+
+    // pivot * x + m[free] * x_free = 0
+    let x_free = pivots
+        .iter()
+        .enumerate()
+        .fold(1, |l, (r, &c)| l / gcd_i64(l, m[r][c]) * m[r][c].abs());
+    let mut x = vec![0; n];
+    x[free] = x_free;
+    for (r, &c) in pivots.iter().enumerate() {
+        x[c] = -m[r][free] * x_free / m[r][c];
+    }
+    let g = x.iter().fold(0, |g, &v| gcd_i64(g, v));
+    Some(x.iter().map(|v| v / g).collect())
+}
+
+fn gcd_i64(a: i64, b: i64) -> i64 {
+    match b {
+        0 => a.abs(),
+        _ => gcd_i64(b, a % b),
+    }
 }
@@ -1,6 +1,18 @@
 use crate::{matter::substance::SubstanceBlock, utils::periodic_table::PeriodicTable};
 use std::collections::HashMap;
 
+// exactly one substance, e.g. formula from a table
+pub fn collect_substance(
+    formula: &str,
+    periodic_table: &PeriodicTable,
+) -> Result<HashMap<String, SubstanceBlock>, &'static str> {
+    let mut e = collect_elements(formula, periodic_table)?;
+    if e.len() != 1 {
+        return Err("There's must be only one substance");
+    }
+    Ok(e.swap_remove(0))
+}
+
 // no regex, as other not-really-needed third-party crates
 pub fn collect_elements(
     reagents: &str,
@@ -48,7 +60,7 @@ pub fn collect_elements(
                     }
                 }
 
-                if check.len() > 0 {
+                if check.len() > 0 && !group.contains(&element) {
                     group.push(element);
                 }
             }
@@ -17,7 +17,7 @@ impl PeriodicTable {
             ("Be".to_string(), Element {charge:4, group:2, period:2, a_rm:9.01218, valencies: vec![1,2], electronegativity:1.57}),
             ("B".to_string(), Element {charge:5, group:13, period:2, a_rm:10.81, valencies: vec![3], electronegativity:2.04}),
             ("C".to_string(), Element {charge:6, group:14, period:2, a_rm:12.011, valencies: vec![2,4], electronegativity:2.55}),
-            ("N".to_string(), Element {charge:7, group:15, period:2, a_rm:14.0067, valencies: vec![1,2,3,4], electronegativity:3.04}),
+            ("N".to_string(), Element {charge:7, group:15, period:2, a_rm:14.0067, valencies: vec![1,2,3,4,5], electronegativity:3.04}),
             ("O".to_string(), Element {charge:8, group:16, period:2, a_rm:15.9994, valencies: vec![2], electronegativity:3.44}),
             ("F".to_string(), Element {charge:9, group:17, period:2, a_rm:18.9984, valencies: vec![1], electronegativity:3.98}),
             ("Ne".to_string(), Element {charge:10, group:18, period:2, a_rm:20.1797, valencies: vec![0], electronegativity:0.0}),
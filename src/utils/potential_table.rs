@@ -0,0 +1,20 @@
+// Standard electrode potentials of Meⁿ⁺/Me couples at 25°C, V: (metal, ion charge, E°).
+// The first couple of a metal is the one it goes to in solutions
+#[rustfmt::skip]
+pub const STANDARD_POTENTIALS: [(&str, i8, f64); 30] = [
+    ("Li", 1, -3.04), ("Cs", 1, -3.03), ("Rb", 1, -2.98), ("K", 1, -2.93),
+    ("Ba", 2, -2.91), ("Sr", 2, -2.89), ("Ca", 2, -2.87), ("Na", 1, -2.71),
+    ("Mg", 2, -2.37), ("Be", 2, -1.85), ("Al", 3, -1.66), ("Ti", 2, -1.63),
+    ("Mn", 2, -1.18), ("Zn", 2, -0.76), ("Cr", 3, -0.74), ("Fe", 2, -0.44),
+    ("Cd", 2, -0.40), ("Co", 2, -0.28), ("Ni", 2, -0.25), ("Sn", 2, -0.14),
+    ("Pb", 2, -0.13), ("Fe", 3, -0.04), ("Sb", 3, 0.15), ("Bi", 3, 0.32),
+    ("Cu", 2, 0.34), ("Ag", 1, 0.80), ("Hg", 2, 0.85), ("Pd", 2, 0.95),
+    ("Pt", 2, 1.18), ("Au", 3, 1.50),
+];
+
+// Couples Meⁿ⁺/Meᵐ⁺ of ions which metals reduce only to the lower ion, V:
+// (metal, higher charge, lower charge, E°). 2FeCl₃ + Cu = 2FeCl₂ + CuCl₂
+#[rustfmt::skip]
+pub const ION_POTENTIALS: [(&str, i8, i8, f64); 2] = [
+    ("Fe", 3, 2, 0.77), ("Sn", 4, 2, 0.15),
+];
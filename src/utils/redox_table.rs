@@ -0,0 +1,22 @@
+// Ions which change their state in acidic medium:
+// (ion, its charge, the product, the product charge), charge 0 - neutral particle: I₂, MnO₂
+#[rustfmt::skip]
+pub const OXIDISERS: [(&str, i8, &str, i8); 2] = [
+    ("MnO4", -1, "Mn", 2), ("Cr2O7", -2, "Cr", 3),
+];
+
+#[rustfmt::skip]
+pub const NEUTRAL_OXIDISERS: [(&str, i8, &str, i8); 2] = [
+    ("MnO4", -1, "MnO2", 0), ("Cr2O7", -2, "Cr(OH)3", 0),
+];
+
+#[rustfmt::skip]
+pub const BASIC_OXIDISERS: [(&str, i8, &str, i8); 1] = [
+    ("MnO4", -1, "MnO4", -2),
+];
+
+#[rustfmt::skip]
+pub const REDUCERS: [(&str, i8, &str, i8); 7] = [
+    ("SO3", -2, "SO4", -2), ("NO2", -1, "NO3", -1), ("Fe", 2, "Fe", 3), ("S", -2, "S", 0),
+    ("I", -1, "I2", 0), ("Br", -1, "Br2", 0), ("Cl", -1, "Cl2", 0),
+];
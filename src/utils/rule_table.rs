@@ -0,0 +1,33 @@
+// Reaction rules, one per line: priority | reagents | conditions | action | type.
+// Reagents are classes in any order, Any for every class, with properties after ":" -
+// metal, nonmetal, hydrogen, water, oxidising, oxidiser, reducer, concentrated, soluble,
+// element=Fe, group=1, formula=KClO3. Conditions are heating, light, melt, catalyst=MnO2,
+// pressure=20000 (at least, kPa), solvent=NH3.
+// Action is a built-in rule (its reagents have to fit it, see BUILTIN_RULES) or products
+// like "KCl + O2", type is only for products and Combination by default.
+// The higher priority wins, then the more specific reagents, then the rule which goes first
+pub const DEFAULT_RULES: &str = "
+100 | Any                                   |   | decomposition
+ 95 | Simple:nonmetal + Simple:nonmetal     |   | nonme_nonme
+ 95 | Simple:hydrogen + Simple:nonmetal     |   | nonme_nonme
+ 90 | Simple:metal + Simple:nonmetal        |   | me_antime
+ 85 | Simple:metal + Oxide:water            |   | me_water
+ 80 | Oxide:water + Oxide                   |   | water
+ 80 | Oxide:water + Hydride                 |   | water
+ 80 | Oxide:water + Peroxide                |   | water
+ 75 | Simple:metal + Acid:oxidising         |   | me_oxidising_acid
+ 70 | Simple:metal + Acid                   |   | me_acid
+ 65 | Simple:metal + Salt                   |   | me_salt
+ 60 | Acid + Base                           |   | acid_base
+ 55 | Salt:oxidiser + Acid:reducer          |   | redox_acidic
+ 50 | Salt + Salt                           |   | exchange
+ 50 | Salt + Acid                           |   | exchange
+ 50 | Salt + Base                           |   | exchange
+ 45 | Oxide + Acid                          |   | oxide_acid
+ 40 | Oxide + Base                          |   | oxide_base
+ 40 | Oxide + Base + Oxide:water            |   | oxide_base
+ 35 | Oxide + Oxide                         |   | oxide_oxide
+ 30 | Simple + Base + Oxide:water           |   | simple_base_water
+ 30 | Salt:oxidiser + Salt + Acid           |   | redox_acidic
+ 30 | Salt:oxidiser + Acid:reducer + Acid   |   | redox_acidic
+";
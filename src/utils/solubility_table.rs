@@ -0,0 +1,53 @@
+// Solubility in water: one row for every anion, one letter for every cation.
+// R - soluble, M - slightly soluble, N - insoluble,
+// D - decomposes in water, X - does not exist
+#[rustfmt::skip]
+pub const CATIONS: [(&str, i8); 22] = [
+    ("H", 1), ("Li", 1), ("K", 1), ("Na", 1), ("NH4", 1), ("Ba", 2), ("Ca", 2), ("Mg", 2),
+    ("Sr", 2), ("Al", 3), ("Cr", 3), ("Fe", 2), ("Fe", 3), ("Ni", 2), ("Co", 2), ("Mn", 2),
+    ("Zn", 2), ("Ag", 1), ("Hg", 2), ("Pb", 2), ("Sn", 2), ("Cu", 2),
+];
+
+#[rustfmt::skip]
+pub const SOLUBILITY_TABLE: [(&str, i8, &str); 13] = [
+    //                     H Li K Na NH4 Ba Ca Mg Sr Al Cr Fe²⁺ Fe³⁺ Ni Co Mn Zn Ag Hg Pb Sn Cu
+    ("OH", -1,      "XRRRRRMNMNNNNNNNNXXNNN"),
+    ("F", -1,       "RMRRRMNNNMMMMRMMMRDNRM"),
+    ("Cl", -1,      "RRRRRRRRRRRRRRRRRNRMRR"),
+    ("Br", -1,      "RRRRRRRRRRRRRRRRRNMMRR"),
+    ("I", -1,       "RRRRRRRRRRRRXRRRRNNNMX"),
+    ("S", -2,       "RRRRRRMDRDDNDNNNNNNNNN"),
+    ("SO3", -2,     "RRRRRNNMNDDNDNNNMNDNDD"),
+    ("SO4", -2,     "RRRRRNMRNRRRRRRRRMDNRR"),
+    ("NO3", -1,     "RRRRRRRRRRRRRRRRRRRRRR"),
+    ("PO4", -3,     "RMRRRNNNNNNNNNNNNNNNNN"),
+    ("CO3", -2,     "DRRRRNNMNDDNDNNNNNDNDD"),
+    ("SiO3", -2,    "NRRRXNNNNNXNXNNNNXXNXN"),
+    ("CH3COO", -1,  "RRRRRRRRRDRRDRRRRMRRDR"),
+];
+
+// Solubility of common compounds, g per 100 g of water at 20°C
+#[rustfmt::skip]
+pub const SOLUBILITY_G_PER_100G: [(&str, f64); 40] = [
+    ("LiOH", 12.8), ("NaOH", 109.0), ("KOH", 112.0), ("Ca(OH)2", 0.173), ("Ba(OH)2", 3.89),
+    ("Mg(OH)2", 0.00069), ("Sr(OH)2", 0.81),
+
+    ("NaCl", 35.9), ("KCl", 34.2), ("NH4Cl", 37.2), ("CaCl2", 74.5), ("BaCl2", 35.8),
+    ("MgCl2", 54.6), ("AgCl", 0.00019), ("PbCl2", 0.99), ("CuCl2", 72.7), ("FeCl3", 91.2),
+    ("KI", 144.0), ("NaI", 178.0), ("PbI2", 0.076), ("AgI", 0.0000003),
+
+    ("Na2SO4", 19.5), ("K2SO4", 11.1), ("MgSO4", 35.1), ("CaSO4", 0.255), ("BaSO4", 0.00024),
+    ("CuSO4", 20.7), ("FeSO4", 26.6), ("ZnSO4", 53.8),
+
+    ("NaNO3", 88.0), ("KNO3", 31.6), ("AgNO3", 222.0), ("Pb(NO3)2", 54.3), ("NH4NO3", 192.0),
+
+    ("Na2CO3", 21.5), ("K2CO3", 111.0), ("NaHCO3", 9.6), ("CaCO3", 0.0013), ("Li2CO3", 1.33),
+
+    ("KMnO4", 6.38),
+];
+
+// Gases at 20°C which leave the solution: hydrogen halides stay dissolved
+#[rustfmt::skip]
+pub const GASES: [&str; 14] = [
+    "H2", "O2", "N2", "F2", "Cl2", "CO", "CO2", "SO2", "H2S", "NH3", "NO", "NO2", "N2O", "CH4",
+];
@@ -0,0 +1,25 @@
+// Standard thermodynamic data at 25°C for substances in their usual state (H₂O is liquid):
+// (formula, ΔH°f kJ/mol, S° J/(mol·K), ΔG°f kJ/mol)
+#[rustfmt::skip]
+pub const THERMODYNAMICS: [(&str, f64, f64, f64); 44] = [
+    ("H2", 0.0, 130.7, 0.0), ("O2", 0.0, 205.2, 0.0), ("N2", 0.0, 191.6, 0.0),
+    ("Cl2", 0.0, 223.1, 0.0), ("C", 0.0, 5.7, 0.0), ("S", 0.0, 32.1, 0.0),
+    ("Fe", 0.0, 27.3, 0.0), ("Al", 0.0, 28.3, 0.0), ("Zn", 0.0, 41.6, 0.0),
+    ("Cu", 0.0, 33.2, 0.0), ("Mg", 0.0, 32.7, 0.0), ("Na", 0.0, 51.3, 0.0),
+    ("Ca", 0.0, 41.6, 0.0), ("K", 0.0, 64.7, 0.0),
+    ("H2O", -285.8, 70.0, -237.1), ("H2O2", -187.8, 109.6, -120.4),
+    ("CO", -110.5, 197.7, -137.2), ("CO2", -393.5, 213.8, -394.4),
+    ("CH4", -74.6, 186.3, -50.5), ("NH3", -45.9, 192.8, -16.4),
+    ("NO", 91.3, 210.8, 87.6), ("NO2", 33.2, 240.1, 51.3), ("N2O", 81.6, 220.0, 103.7),
+    ("SO2", -296.8, 248.2, -300.1), ("SO3", -395.7, 256.8, -371.1),
+    ("HCl", -92.3, 186.9, -95.3), ("H2S", -20.6, 205.8, -33.4),
+    ("Fe2O3", -824.2, 87.4, -742.2), ("Al2O3", -1675.7, 50.9, -1582.3),
+    ("CaO", -634.9, 38.1, -603.3), ("MgO", -601.6, 27.0, -569.3),
+    ("ZnO", -350.5, 43.7, -320.5), ("CuO", -157.3, 42.6, -129.7),
+    ("Na2O", -414.2, 75.1, -375.5), ("CaCO3", -1207.6, 91.7, -1129.1),
+    ("NaCl", -411.2, 72.1, -384.1), ("KCl", -436.5, 82.6, -408.5),
+    ("NaOH", -425.8, 64.4, -379.7), ("Ca(OH)2", -985.2, 83.4, -897.5),
+    ("KClO3", -397.7, 143.1, -296.3), ("H2SO4", -814.0, 156.9, -690.0),
+    ("HNO3", -174.1, 155.6, -80.7), ("NH4Cl", -314.4, 94.6, -202.9),
+    ("ZnCl2", -415.1, 111.5, -369.4),
+];
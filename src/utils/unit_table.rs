@@ -0,0 +1,57 @@
+// Units and how many base units (the first one) they are
+#[rustfmt::skip]
+pub const MASS_UNITS: [(&str, f64); 4] = [
+    ("g", 1.0), ("mg", 1e-3), ("kg", 1e3), ("t", 1e6),
+];
+
+#[rustfmt::skip]
+pub const AMOUNT_UNITS: [(&str, f64); 3] = [
+    ("mol", 1.0), ("mmol", 1e-3), ("kmol", 1e3),
+];
+
+#[rustfmt::skip]
+pub const VOLUME_UNITS: [(&str, f64); 7] = [
+    ("L", 1.0), ("l", 1.0), ("dm3", 1.0), ("mL", 1e-3), ("ml", 1e-3), ("cm3", 1e-3), ("m3", 1e3),
+];
+
+#[rustfmt::skip]
+pub const PRESSURE_UNITS: [(&str, f64); 7] = [
+    ("kPa", 1.0), ("Pa", 1e-3), ("MPa", 1e3), ("atm", 101.325), ("bar", 100.0),
+    ("mmHg", 0.133322), ("torr", 0.133322),
+];
+
+#[rustfmt::skip]
+pub const ENERGY_UNITS: [(&str, f64); 4] = [
+    ("kJ", 1.0), ("J", 1e-3), ("kcal", 4.184), ("cal", 4.184e-3),
+];
+
+#[rustfmt::skip]
+pub const ENTROPY_UNITS: [(&str, f64); 2] = [
+    ("J/K", 1.0), ("kJ/K", 1e3),
+];
+
+#[rustfmt::skip]
+pub const MOLARITY_UNITS: [(&str, f64); 4] = [
+    ("M", 1.0), ("mol/L", 1.0), ("mM", 1e-3), ("mmol/L", 1e-3),
+];
+
+#[rustfmt::skip]
+pub const MOLALITY_UNITS: [(&str, f64); 2] = [
+    ("mol/kg", 1.0), ("mmol/kg", 1e-3),
+];
+
+#[rustfmt::skip]
+pub const MOLAR_MASS_UNITS: [(&str, f64); 2] = [
+    ("g/mol", 1.0), ("kg/mol", 1e3),
+];
+
+#[rustfmt::skip]
+pub const DENSITY_UNITS: [(&str, f64); 6] = [
+    ("g/L", 1.0), ("kg/m3", 1.0), ("g/mL", 1e3), ("g/cm3", 1e3), ("kg/L", 1e3), ("mg/L", 1e-3),
+];
+
+// (unit, factor, offset): K = value × factor + offset
+#[rustfmt::skip]
+pub const TEMPERATURE_UNITS: [(&str, f64, f64); 4] = [
+    ("K", 1.0, 0.0), ("°C", 1.0, 273.15), ("C", 1.0, 273.15), ("°F", 5.0 / 9.0, 273.15 - 32.0 * 5.0 / 9.0),
+];